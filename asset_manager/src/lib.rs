@@ -0,0 +1,166 @@
+// Finds the files the game is made of, and notices when they change so
+// they can be reloaded while the game is running
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+// Names the asset directory, overriding the search next to the executable
+pub const ASSETS_VARIABLE: &str = "RIDEWAYS_ASSETS";
+
+// The name of the asset directory when it is searched for
+pub const ASSETS_DIR_NAME: &str = "assets";
+
+// Seconds between checks for changed files
+const POLL_INTERVAL: f32 = 0.5;
+
+pub struct Assets {
+    root: PathBuf,
+    watched: BTreeMap<String, Option<SystemTime>>,
+    since_poll: f32,
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+impl Assets {
+    pub fn new(root: &Path) -> Self {
+        Assets {
+            root: root.to_path_buf(),
+            watched: BTreeMap::new(),
+            since_poll: 0.0,
+        }
+    }
+
+    // Uses the directory given on the command line if there is one, then
+    // the one named by the environment variable. Otherwise looks for an
+    // assets directory next to the executable or in one of the directories
+    // above it, which finds it when running from the cargo target directory,
+    // and finally in the working directory.
+    pub fn find(command_line: Option<&Path>) -> Result<Self, Box<dyn Error>> {
+        let variable = std::env::var_os(ASSETS_VARIABLE).map(PathBuf::from);
+        if let Some(dir) = command_line.map(Path::to_path_buf).or(variable) {
+            if !dir.is_dir() {
+                return Err(format!("Asset directory {} doesn't exist", dir.display()).into());
+            }
+            return Ok(Assets::new(&dir));
+        }
+        let exe = std::env::current_exe()?;
+        exe.ancestors()
+            .skip(1)
+            .map(|dir| dir.join(ASSETS_DIR_NAME))
+            .chain(std::iter::once(PathBuf::from(ASSETS_DIR_NAME)))
+            .find(|dir| dir.is_dir())
+            .map(|dir| Assets::new(&dir))
+            .ok_or_else(|| {
+                format!(
+                    "No {} directory found, set {} to point to it",
+                    ASSETS_DIR_NAME, ASSETS_VARIABLE
+                )
+                .into()
+            })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn path(&self, name: &str) -> PathBuf {
+        self.root.join(name)
+    }
+
+    pub fn exists(&self, name: &str) -> bool {
+        self.path(name).is_file()
+    }
+
+    // Remembers the modification time of the file, so changes to it are
+    // reported by changed_files
+    pub fn watch(&mut self, name: &str) {
+        let time = modified(&self.path(name));
+        self.watched.insert(name.to_string(), time);
+    }
+
+    // The watched files that have been modified, created or deleted since
+    // they were watched or last reported
+    pub fn changed_files(&mut self) -> Vec<String> {
+        let root = &self.root;
+        self.watched
+            .iter_mut()
+            .filter_map(|(name, time)| {
+                let now = modified(&root.join(name.as_str()));
+                if now != *time {
+                    *time = now;
+                    Some(name.clone())
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    // Calls changed_files now and then, to be called every frame
+    pub fn poll(&mut self, elapsed_seconds: f32) -> Vec<String> {
+        self.since_poll += elapsed_seconds;
+        if self.since_poll < POLL_INTERVAL {
+            return vec![];
+        }
+        self.since_poll = 0.0;
+        self.changed_files()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn touch(path: &Path, time: SystemTime) {
+        let file = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .unwrap();
+        file.set_modified(time).unwrap();
+    }
+
+    #[test]
+    fn test_changed_files() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let start = SystemTime::now() - Duration::from_secs(100);
+        touch(&dir.join("ufo.png"), start);
+        touch(&dir.join("glow.png"), start);
+
+        let mut assets = Assets::new(dir);
+        assets.watch("ufo.png");
+        assets.watch("glow.png");
+        assets.watch("ufo.json");
+        assert!(assets.changed_files().is_empty());
+
+        touch(&dir.join("ufo.png"), start + Duration::from_secs(10));
+        touch(&dir.join("ufo.json"), start);
+        assert_eq!(assets.changed_files(), vec!["ufo.json", "ufo.png"]);
+        assert!(assets.changed_files().is_empty());
+
+        std::fs::remove_file(dir.join("glow.png")).unwrap();
+        assert_eq!(assets.changed_files(), vec!["glow.png"]);
+    }
+
+    #[test]
+    fn test_poll_waits() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut assets = Assets::new(dir.path());
+        assets.watch("starfield.json");
+        touch(&dir.path().join("starfield.json"), SystemTime::now());
+        assert!(assets.poll(0.1).is_empty());
+        assert_eq!(assets.poll(POLL_INTERVAL), vec!["starfield.json"]);
+    }
+
+    #[test]
+    fn test_missing_directory() {
+        let result = Assets::find(Some(Path::new("/no/such/assets")));
+        assert!(result.is_err());
+    }
+}
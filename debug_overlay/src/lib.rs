@@ -0,0 +1,346 @@
+// Information for finding out why the game behaves as it does: frame
+// times, how many entities have each component, how long each system
+// takes, and the rectangles and velocities collisions are checked with.
+use std::collections::VecDeque;
+use std::error::Error;
+
+use ecs_components::{HarmsAliens, HarmsPlayer, IsAlien, IsFast, IsPlayer, Position, Velocity};
+use geometry::{Rect, RectSize};
+use render::{Color, FontType, Renderer, TextPosition};
+use shared_types::{Camera, ElapsedSeconds, SystemTimes};
+use specs::{Join, World, WorldExt};
+
+// Frames shown in the frame time graph, one pixel column each
+const FRAME_HISTORY: usize = 150;
+
+// The frame time reaching the top of the graph, and its height
+const GRAPH_MAX_SECONDS: f32 = 1.0 / 20.0;
+const GRAPH_HEIGHT: f32 = 60.0;
+
+// Frames slower than this are drawn in the warning color
+const TARGET_FRAME_SECONDS: f32 = 1.0 / 55.0;
+
+// Where the text columns start, and their width
+const PANEL_TOP: f32 = 40.0;
+const COLUMN_WIDTH: f32 = 260.0;
+const MARGIN: f32 = 10.0;
+
+// Velocity lines show where an entity will be after this many seconds
+const VELOCITY_SECONDS: f32 = 0.1;
+
+const TEXT_COLOR: Color = Color::rgb(230, 230, 230);
+const PANEL_COLOR: Color = Color::rgba(0, 0, 0, 170);
+const GRAPH_COLOR: Color = Color::rgb(80, 220, 80);
+const SLOW_FRAME_COLOR: Color = Color::rgb(230, 60, 60);
+const PLAYER_COLOR: Color = Color::rgb(60, 230, 60);
+const ALIEN_COLOR: Color = Color::rgb(230, 60, 60);
+const SHOT_COLOR: Color = Color::rgb(240, 220, 60);
+// The rectangle a fast shot is swept from
+const SWEEP_COLOR: Color = Color::rgba(240, 220, 60, 90);
+const VELOCITY_COLOR: Color = Color::rgb(60, 200, 240);
+
+pub struct DebugOverlay {
+    visible: bool,
+    frame_times: VecDeque<f32>,
+}
+
+impl DebugOverlay {
+    pub fn new() -> Self {
+        DebugOverlay {
+            visible: false,
+            frame_times: VecDeque::with_capacity(FRAME_HISTORY),
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    // Frames are recorded while hidden too, so the graph is full when shown
+    pub fn record_frame(&mut self, seconds: f32) {
+        if self.frame_times.len() == FRAME_HISTORY {
+            self.frame_times.pop_front();
+        }
+        self.frame_times.push_back(seconds);
+    }
+
+    // Frames per second, averaged over the recorded frames
+    pub fn fps(&self) -> f32 {
+        let total: f32 = self.frame_times.iter().sum();
+        if total > 0.0 {
+            self.frame_times.len() as f32 / total
+        } else {
+            0.0
+        }
+    }
+
+    pub fn draw(&self, world: &World, renderer: &mut impl Renderer) -> Result<(), Box<dyn Error>> {
+        if !self.visible {
+            return Ok(());
+        }
+        draw_colliders(world, renderer);
+
+        let mut frame_lines = vec![format!("FPS {:.0}", self.fps())];
+        if let Some(last) = self.frame_times.back() {
+            frame_lines.push(format!("Frame {:.1} ms", last * 1000.0));
+        }
+        let bottom = draw_column(renderer, 0, &frame_lines)?;
+        self.draw_graph(renderer, bottom);
+
+        let entities = world.entities().join().count();
+        let mut entity_lines = vec![format!("Entities {}", entities)];
+        entity_lines.extend(
+            ecs_components::component_counts(world)
+                .into_iter()
+                .filter(|(_, count)| *count > 0)
+                .map(|(name, count)| format!("{} {}", name, count)),
+        );
+        draw_column(renderer, 1, &entity_lines)?;
+
+        if let Some(times) = world.try_fetch::<SystemTimes>() {
+            let mut system_lines = vec!["Systems (ms)".to_string()];
+            system_lines.extend(
+                times
+                    .times()
+                    .into_iter()
+                    .map(|(name, seconds)| format!("{} {:.2}", name, seconds * 1000.0)),
+            );
+            draw_column(renderer, 2, &system_lines)?;
+        }
+        Ok(())
+    }
+
+    // One bar per frame, newest to the right
+    fn draw_graph(&self, renderer: &mut impl Renderer, top: f32) {
+        let left = MARGIN;
+        renderer.fill_rect(
+            Rect::new(
+                (left, top).into(),
+                RectSize(FRAME_HISTORY as f32, GRAPH_HEIGHT),
+            ),
+            PANEL_COLOR,
+        );
+        let bottom = top + GRAPH_HEIGHT;
+        let start = FRAME_HISTORY - self.frame_times.len();
+        for (index, seconds) in self.frame_times.iter().enumerate() {
+            let height = (seconds / GRAPH_MAX_SECONDS).min(1.0) * GRAPH_HEIGHT;
+            let color = if *seconds > TARGET_FRAME_SECONDS {
+                SLOW_FRAME_COLOR
+            } else {
+                GRAPH_COLOR
+            };
+            renderer.fill_rect(
+                Rect::new(
+                    (left + (start + index) as f32, bottom - height).into(),
+                    RectSize(1.0, height),
+                ),
+                color,
+            );
+        }
+    }
+}
+
+impl Default for DebugOverlay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Draws the lines of text in the column on a dark background, and returns
+// where the column ends
+fn draw_column(
+    renderer: &mut impl Renderer,
+    column: usize,
+    lines: &[String],
+) -> Result<f32, Box<dyn Error>> {
+    let (_, line_height) = renderer.text_size("Ag", FontType::Small)?;
+    let left = MARGIN + column as f32 * (COLUMN_WIDTH + MARGIN);
+    let height = line_height as f32 * lines.len() as f32;
+    renderer.fill_rect(
+        Rect::new((left, PANEL_TOP).into(), RectSize(COLUMN_WIDTH, height)),
+        PANEL_COLOR,
+    );
+    for (index, line) in lines.iter().enumerate() {
+        let top = PANEL_TOP + line_height as f32 * index as f32;
+        renderer.draw_text(
+            line,
+            TextPosition::TopLeft((left + 4.0) as u32, top as u32),
+            TEXT_COLOR,
+            FontType::Small,
+        )?;
+    }
+    Ok(PANEL_TOP + height)
+}
+
+// Outlines the rectangles collisions are checked with, and draws the
+// velocity of everything that can collide. Fast shots are checked along
+// the path from where they were last frame, which is outlined too.
+fn draw_colliders(world: &World, renderer: &mut impl Renderer) {
+    type ColliderData<'a> = (
+        specs::Read<'a, Camera>,
+        Option<specs::Read<'a, ElapsedSeconds>>,
+        specs::ReadStorage<'a, Position>,
+        specs::ReadStorage<'a, Velocity>,
+        specs::ReadStorage<'a, IsPlayer>,
+        specs::ReadStorage<'a, IsAlien>,
+        specs::ReadStorage<'a, HarmsAliens>,
+        specs::ReadStorage<'a, HarmsPlayer>,
+        specs::ReadStorage<'a, IsFast>,
+    );
+    let (
+        camera,
+        delta_time,
+        positions,
+        velocities,
+        players,
+        aliens,
+        harms_aliens,
+        harms_player,
+        fast,
+    ): ColliderData = world.system_data();
+    // Nothing has moved yet while the menu shows
+    let delta_time = delta_time.map_or(0.0, |delta_time| delta_time.0);
+
+    for (position, velocity, player, alien, harms_aliens, harms_player, fast) in (
+        &positions,
+        velocities.maybe(),
+        players.maybe(),
+        aliens.maybe(),
+        harms_aliens.maybe(),
+        harms_player.maybe(),
+        fast.maybe(),
+    )
+        .join()
+    {
+        let color = if player.is_some() {
+            PLAYER_COLOR
+        } else if alien.is_some() {
+            ALIEN_COLOR
+        } else if harms_aliens.is_some() || harms_player.is_some() {
+            SHOT_COLOR
+        } else {
+            continue;
+        };
+        outline(renderer, &camera, position.rect, color);
+
+        if let Some(velocity) = velocity {
+            let center = position.rect.center();
+            let ahead = (
+                center.0 + velocity.x * VELOCITY_SECONDS,
+                center.1 + velocity.y * VELOCITY_SECONDS,
+            );
+            renderer.draw_line(
+                camera.world_to_screen(center),
+                camera.world_to_screen(ahead.into()),
+                VELOCITY_COLOR,
+            );
+            if fast.is_some() {
+                let previous = position.rect.translated(-velocity.vec2() * delta_time);
+                outline(renderer, &camera, previous, SWEEP_COLOR);
+            }
+        }
+    }
+}
+
+fn outline(renderer: &mut impl Renderer, camera: &Camera, rect: Rect, color: Color) {
+    let topleft = camera.world_to_screen(rect.topleft());
+    let (width, height) = (rect.width() * camera.zoom, rect.height() * camera.zoom);
+    let (left, top) = (topleft.0, topleft.1);
+    let edges = [
+        Rect::new((left, top).into(), RectSize(width, 1.0)),
+        Rect::new((left, top + height - 1.0).into(), RectSize(width, 1.0)),
+        Rect::new((left, top).into(), RectSize(1.0, height)),
+        Rect::new((left + width - 1.0, top).into(), RectSize(1.0, height)),
+    ];
+    for edge in edges.iter() {
+        renderer.fill_rect(*edge, color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use software_graphics::SoftwareRenderer;
+    use specs::Builder;
+    use std::path::Path;
+
+    #[test]
+    fn test_fps() {
+        let mut overlay = DebugOverlay::new();
+        assert_eq!(overlay.fps(), 0.0);
+        for _ in 0..FRAME_HISTORY * 2 {
+            overlay.record_frame(0.04);
+        }
+        overlay.record_frame(0.01);
+        assert_eq!(overlay.frame_times.len(), FRAME_HISTORY);
+        assert!((overlay.fps() - 25.1).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_draws_colliders_when_visible() {
+        let assets = Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets");
+        let mut renderer = SoftwareRenderer::with_bundled_font((900, 600), &assets).unwrap();
+
+        let mut world = World::new();
+        ecs_components::register_components(&mut world);
+        world.insert(Camera::new(RectSize(900.0, 600.0)));
+        world.insert(ElapsedSeconds(0.0));
+        world
+            .create_entity()
+            .with(Position {
+                rect: Rect::new((600.0, 400.0).into(), RectSize(40.0, 20.0)),
+            })
+            .with(Velocity { x: 0.0, y: 500.0 })
+            .with(IsPlayer)
+            .build();
+
+        let mut overlay = DebugOverlay::new();
+        overlay.record_frame(0.016);
+        let color_at = |renderer: &SoftwareRenderer, x, y| {
+            let pixel = renderer.image().get_pixel(x, y).0;
+            Color::rgb(pixel[0], pixel[1], pixel[2])
+        };
+
+        renderer.clear();
+        overlay.draw(&world, &mut renderer).unwrap();
+        assert_eq!(color_at(&renderer, 600, 410), Color::rgb(0, 0, 0));
+
+        overlay.toggle();
+        renderer.clear();
+        overlay.draw(&world, &mut renderer).unwrap();
+        assert_eq!(color_at(&renderer, 600, 410), PLAYER_COLOR);
+        assert_eq!(color_at(&renderer, 639, 410), PLAYER_COLOR);
+        assert_eq!(color_at(&renderer, 620, 400), PLAYER_COLOR);
+        assert_eq!(color_at(&renderer, 620, 405), Color::rgb(0, 0, 0));
+        assert_eq!(color_at(&renderer, 620, 440), VELOCITY_COLOR);
+    }
+
+    #[test]
+    fn test_draws_before_the_game_has_run() {
+        let assets = Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets");
+        let mut renderer = SoftwareRenderer::with_bundled_font((900, 600), &assets).unwrap();
+
+        let mut world = World::new();
+        ecs_components::register_components(&mut world);
+        world.insert(Camera::new(RectSize(900.0, 600.0)));
+        world
+            .create_entity()
+            .with(Position {
+                rect: Rect::new((600.0, 400.0).into(), RectSize(10.0, 4.0)),
+            })
+            .with(Velocity { x: 1500.0, y: 0.0 })
+            .with(HarmsAliens)
+            .with(IsFast)
+            .build();
+
+        let mut overlay = DebugOverlay::new();
+        overlay.toggle();
+        overlay.draw(&world, &mut renderer).unwrap();
+        let pixel = renderer.image().get_pixel(600, 400).0;
+        assert_eq!(Color::rgb(pixel[0], pixel[1], pixel[2]), SHOT_COLOR);
+    }
+}
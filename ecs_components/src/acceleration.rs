@@ -0,0 +1,17 @@
+use geometry::Vec2;
+use specs::{Component, VecStorage};
+use specs_derive::Component;
+
+// Change of velocity in pixels per second squared
+#[derive(Debug, Component, Default)]
+#[storage(VecStorage)]
+pub struct Acceleration {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Acceleration {
+    pub fn vec2(&self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+}
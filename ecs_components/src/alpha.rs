@@ -1,10 +1,7 @@
 use specs::{Component, VecStorage};
 use specs_derive::Component;
 
-type SpawnerLifetime = f32;
-
+// How opaque the entity is drawn, from 0 to 1
 #[derive(Debug, Component, Copy, Clone)]
 #[storage(VecStorage)]
-pub enum SpawnerKind {
-    Fire(SpawnerLifetime),
-}
+pub struct Alpha(pub f32);
@@ -0,0 +1,155 @@
+use specs::{Component, VecStorage};
+use specs_derive::Component;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct AnimationFrame {
+    // Index into the frames of the sprite sheet
+    pub index: usize,
+    pub seconds: f32,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum AnimationMode {
+    Loop,
+    Once,
+}
+
+// What happens to the entity when an animation played Once has shown its
+// last frame
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum OnFinish {
+    // Keep showing the last frame
+    Hold,
+    RemoveAnimation,
+    DeleteEntity,
+}
+
+#[derive(Debug, Component, Clone)]
+#[storage(VecStorage)]
+pub struct Animation {
+    pub frames: Vec<AnimationFrame>,
+    pub mode: AnimationMode,
+    pub on_finish: OnFinish,
+    current: usize,
+    seconds_in_frame: f32,
+    finished: bool,
+}
+
+impl Animation {
+    pub fn new(frames: Vec<AnimationFrame>, mode: AnimationMode, on_finish: OnFinish) -> Self {
+        Animation {
+            frames,
+            mode,
+            on_finish,
+            current: 0,
+            seconds_in_frame: 0.0,
+            finished: false,
+        }
+    }
+
+    // An animation showing each of the sprite sheet frames for the same time
+    pub fn uniform(indices: &[usize], seconds: f32, mode: AnimationMode) -> Self {
+        let frames = indices
+            .iter()
+            .map(|&index| AnimationFrame { index, seconds })
+            .collect();
+        Animation::new(frames, mode, OnFinish::Hold)
+    }
+
+    pub fn with_on_finish(mut self, on_finish: OnFinish) -> Self {
+        self.on_finish = on_finish;
+        self
+    }
+
+    // The sprite sheet frame to draw
+    pub fn frame_index(&self) -> usize {
+        self.frames.get(self.current).map_or(0, |f| f.index)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    // Moves the animation seconds forward. Returns true when an animation
+    // played Once reaches its end during this step.
+    pub fn advance(&mut self, seconds: f32) -> bool {
+        if self.finished || self.frames.is_empty() {
+            return false;
+        }
+        self.seconds_in_frame += seconds;
+        while self.seconds_in_frame >= self.frames[self.current].seconds {
+            let frame_seconds = self.frames[self.current].seconds;
+            if self.current + 1 < self.frames.len() {
+                self.current += 1;
+            } else if self.mode == AnimationMode::Loop {
+                self.current = 0;
+            } else {
+                self.finished = true;
+                return true;
+            }
+            self.seconds_in_frame -= frame_seconds;
+            // Frames without a duration would otherwise loop forever
+            if frame_seconds <= 0.0 && self.current == 0 {
+                break;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_loop() {
+        let mut animation = Animation::uniform(&[3, 4, 5], 0.1, AnimationMode::Loop);
+        assert_eq!(animation.frame_index(), 3);
+        assert!(!animation.advance(0.15));
+        assert_eq!(animation.frame_index(), 4);
+        assert!(!animation.advance(0.1));
+        assert_eq!(animation.frame_index(), 5);
+        assert!(!animation.advance(0.1));
+        assert_eq!(animation.frame_index(), 3);
+        assert!(!animation.is_finished());
+    }
+
+    #[test]
+    fn test_once_finishes_on_last_frame() {
+        let mut animation = Animation::uniform(&[0, 1], 0.5, AnimationMode::Once)
+            .with_on_finish(OnFinish::DeleteEntity);
+        assert!(!animation.advance(0.6));
+        assert_eq!(animation.frame_index(), 1);
+        assert!(animation.advance(0.6));
+        assert!(animation.is_finished());
+        assert_eq!(animation.frame_index(), 1);
+        assert!(!animation.advance(0.6));
+    }
+
+    #[test]
+    fn test_individual_frame_durations() {
+        let frames = vec![
+            AnimationFrame {
+                index: 0,
+                seconds: 1.0,
+            },
+            AnimationFrame {
+                index: 1,
+                seconds: 0.1,
+            },
+        ];
+        let mut animation = Animation::new(frames, AnimationMode::Loop, OnFinish::Hold);
+        animation.advance(0.9);
+        assert_eq!(animation.frame_index(), 0);
+        animation.advance(0.15);
+        assert_eq!(animation.frame_index(), 1);
+        animation.advance(0.1);
+        assert_eq!(animation.frame_index(), 0);
+    }
+
+    #[test]
+    fn test_zero_durations_do_not_hang() {
+        let mut animation = Animation::uniform(&[0, 1], 0.0, AnimationMode::Loop);
+        animation.advance(1.0);
+    }
+}
@@ -0,0 +1,53 @@
+use specs::{Component, VecStorage};
+use specs_derive::Component;
+
+// Opacity while a blinking entity is in its dim half
+const DIM_OPACITY: f32 = 0.2;
+// Opacity of a blinking entity when flashing is reduced
+const STEADY_OPACITY: f32 = 0.5;
+
+// Makes the entity switch between fully shown and dim every interval
+// seconds
+#[derive(Debug, Component, Copy, Clone)]
+#[storage(VecStorage)]
+pub struct Blink {
+    pub interval: f32,
+    pub elapsed: f32,
+}
+
+impl Blink {
+    pub fn new(interval: f32) -> Self {
+        Blink {
+            interval,
+            elapsed: 0.0,
+        }
+    }
+
+    // When flashing is reduced the entity is shown steadily at half
+    // opacity instead
+    pub fn opacity(&self, reduce_flashing: bool) -> f32 {
+        if reduce_flashing {
+            STEADY_OPACITY
+        } else if ((self.elapsed / self.interval) as u32).is_multiple_of(2) {
+            1.0
+        } else {
+            DIM_OPACITY
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opacity() {
+        let mut blink = Blink::new(0.1);
+        assert_eq!(blink.opacity(false), 1.0);
+        blink.elapsed = 0.15;
+        assert_eq!(blink.opacity(false), DIM_OPACITY);
+        blink.elapsed = 0.25;
+        assert_eq!(blink.opacity(false), 1.0);
+        assert_eq!(blink.opacity(true), STEADY_OPACITY);
+    }
+}
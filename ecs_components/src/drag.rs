@@ -0,0 +1,10 @@
+use specs::{Component, VecStorage};
+use specs_derive::Component;
+
+// Slows an entity down exponentially. Without acceleration the speed is
+// reduced by a factor of e every 1 / rate seconds.
+#[derive(Debug, Component)]
+#[storage(VecStorage)]
+pub struct Drag {
+    pub rate: f32,
+}
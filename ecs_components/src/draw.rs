@@ -1,8 +1,9 @@
+use shared_types::Color;
 use specs::{Component, VecStorage};
 use specs_derive::Component;
 
 #[derive(Debug, Component)]
 #[storage(VecStorage)]
 pub enum Draw {
-    Star(i16),
+    Star { radius: i16, color: Color },
 }
@@ -0,0 +1,24 @@
+use specs::{Component, VecStorage};
+use specs_derive::Component;
+
+// Sends out particles as described by the named particle effect, from the
+// center of the entity
+#[derive(Debug, Component, Clone)]
+#[storage(VecStorage)]
+pub struct Emitter {
+    pub effect: String,
+    // Particles owed from earlier frames, when the rate doesn't divide
+    // evenly into frames
+    pub pending: f32,
+    pub burst_done: bool,
+}
+
+impl Emitter {
+    pub fn new(effect: &str) -> Self {
+        Emitter {
+            effect: effect.to_string(),
+            pending: 0.0,
+            burst_done: false,
+        }
+    }
+}
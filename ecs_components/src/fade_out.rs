@@ -0,0 +1,31 @@
+use specs::{Component, VecStorage};
+use specs_derive::Component;
+
+use crate::Lifetime;
+
+// Fades the entity out during the last seconds of its lifetime
+#[derive(Debug, Component, Copy, Clone)]
+#[storage(VecStorage)]
+pub struct FadeOut {
+    pub seconds: f32,
+}
+
+impl FadeOut {
+    // How visible the entity is, from 0 to 1
+    pub fn opacity(&self, lifetime: &Lifetime) -> f32 {
+        (lifetime.seconds / self.seconds).clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opacity() {
+        let fade = FadeOut { seconds: 0.5 };
+        assert_eq!(fade.opacity(&Lifetime { seconds: 2.0 }), 1.0);
+        assert_eq!(fade.opacity(&Lifetime { seconds: 0.25 }), 0.5);
+        assert_eq!(fade.opacity(&Lifetime { seconds: -0.1 }), 0.0);
+    }
+}
@@ -1,6 +1,22 @@
-use specs::{Component, NullStorage};
+use specs::{Component, VecStorage};
 use specs_derive::Component;
 
-#[derive(Debug, Component, Default)]
-#[storage(NullStorage)]
-pub struct IsAlien;
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AlienKind {
+    Ufo,
+}
+
+impl AlienKind {
+    // Kept in the run history and used in string ids, so it must not change
+    pub fn id(&self) -> &'static str {
+        match self {
+            AlienKind::Ufo => "ufo",
+        }
+    }
+}
+
+#[derive(Debug, Component, Copy, Clone)]
+#[storage(VecStorage)]
+pub struct IsAlien {
+    pub kind: AlienKind,
+}
@@ -0,0 +1,9 @@
+use specs::{Component, NullStorage};
+use specs_derive::Component;
+
+// Entities moving so fast that they may pass through others between two
+// frames. Collisions for these are checked along the path moved since the
+// last frame instead of only at the current position.
+#[derive(Debug, Component, Default)]
+#[storage(NullStorage)]
+pub struct IsFast;
@@ -0,0 +1,20 @@
+use serde::Deserialize;
+use specs::{Component, VecStorage};
+use specs_derive::Component;
+
+// The layers are drawn in the order they are listed here, so entities on
+// later layers are drawn on top of those on earlier layers. Entities
+// without a layer are drawn with the effects.
+#[derive(
+    Debug, Component, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize,
+)]
+#[storage(VecStorage)]
+pub enum Layer {
+    Background,
+    Enemies,
+    Projectiles,
+    Player,
+    #[default]
+    Effects,
+    Hud,
+}
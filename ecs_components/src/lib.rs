@@ -1,33 +1,73 @@
+mod acceleration;
+mod alpha;
+mod animation;
+mod blink;
+mod drag;
 mod draw;
+mod emitter;
+mod fade_out;
 mod harms_aliens;
 mod harms_player;
 mod invincibility;
 mod is_alien;
 mod is_explosion;
+mod is_fast;
 mod is_player;
 mod keep_inside;
+mod layer;
 mod lifetime;
+mod max_speed;
 mod movement_kind;
+mod particle;
+mod particle_effects;
 mod position;
 mod reap_when_outside;
-mod spawner_kind;
+mod registry;
+mod rotation;
+mod rotation_kind;
 mod sprite;
+mod starfield;
+mod text;
+mod tiled;
+mod tint;
+mod twinkle;
 mod velocity;
 mod world_queries;
+mod z_order;
 
+pub use acceleration::Acceleration;
+pub use alpha::Alpha;
+pub use animation::{Animation, AnimationFrame, AnimationMode, OnFinish};
+pub use blink::Blink;
+pub use drag::Drag;
 pub use draw::Draw;
+pub use emitter::Emitter;
+pub use fade_out::FadeOut;
 pub use harms_aliens::HarmsAliens;
 pub use harms_player::HarmsPlayer;
 pub use invincibility::Invincibility;
-pub use is_alien::IsAlien;
+pub use is_alien::{AlienKind, IsAlien};
 pub use is_explosion::IsExplosion;
+pub use is_fast::IsFast;
 pub use is_player::IsPlayer;
 pub use keep_inside::KeepInside;
+pub use layer::Layer;
 pub use lifetime::Lifetime;
+pub use max_speed::MaxSpeed;
 pub use movement_kind::MovementKind;
+pub use particle::Particle;
+pub use particle_effects::{ColorStop, EmitterDefinition, ParticleEffects, Range, SizeStop};
 pub use position::Position;
 pub use reap_when_outside::ReapWhenOutside;
-pub use spawner_kind::SpawnerKind;
+pub use registry::{component_counts, register_components};
+pub use rotation::Rotation;
+pub use rotation_kind::RotationKind;
 pub use sprite::Sprite;
+pub use starfield::{ImageLayer, StarLayer, Starfield, StarfieldLayer};
+pub use text::Text;
+pub use tiled::Tiled;
+pub use tint::Tint;
+pub use twinkle::Twinkle;
 pub use velocity::Velocity;
 pub use world_queries::{all_explosions_gone, get_playing_state, is_player_dead};
+pub use z_order::ZOrder;
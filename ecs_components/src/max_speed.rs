@@ -0,0 +1,8 @@
+use specs::{Component, VecStorage};
+use specs_derive::Component;
+
+#[derive(Debug, Component)]
+#[storage(VecStorage)]
+pub struct MaxSpeed {
+    pub speed: f32,
+}
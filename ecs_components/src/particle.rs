@@ -0,0 +1,23 @@
+use shared_types::Color;
+use specs::{Component, VecStorage};
+use specs_derive::Component;
+
+// A particle changes color and size over its life as described by the
+// particle effect that made it. The particle is removed when its age
+// reaches its lifetime.
+#[derive(Debug, Component, Clone)]
+#[storage(VecStorage)]
+pub struct Particle {
+    pub effect: String,
+    pub age: f32,
+    pub lifetime: f32,
+    pub color: Color,
+    pub scale: f32,
+}
+
+impl Particle {
+    // How far through its life the particle is, from 0 to 1
+    pub fn progress(&self) -> f32 {
+        (self.age / self.lifetime).min(1.0)
+    }
+}
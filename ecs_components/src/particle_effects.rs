@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::Path;
+
+use rand::Rng;
+use serde::Deserialize;
+use shared_types::Color;
+
+use crate::{Layer, Sprite};
+
+#[derive(Debug, Copy, Clone, Deserialize)]
+pub struct Range {
+    pub min: f32,
+    pub max: f32,
+}
+
+impl Range {
+    pub fn sample(&self, rng: &mut impl Rng) -> f32 {
+        if self.min < self.max {
+            rng.gen_range(self.min..=self.max)
+        } else {
+            self.min
+        }
+    }
+}
+
+// The color of a particle at a point in its life, where 0 is when it is
+// created and 1 is when it dies. The alpha of the color fades it out.
+#[derive(Debug, Copy, Clone, Deserialize)]
+pub struct ColorStop {
+    pub at: f32,
+    pub color: Color,
+}
+
+// The size of a particle relative to the size of its sprite at a point
+// in its life
+#[derive(Debug, Copy, Clone, Deserialize)]
+pub struct SizeStop {
+    pub at: f32,
+    pub scale: f32,
+}
+
+fn white() -> Vec<ColorStop> {
+    vec![ColorStop {
+        at: 0.0,
+        color: Color::WHITE,
+    }]
+}
+
+fn full_size() -> Vec<SizeStop> {
+    vec![SizeStop {
+        at: 0.0,
+        scale: 1.0,
+    }]
+}
+
+fn full_circle() -> f32 {
+    360.0
+}
+
+// Describes the particles made by an emitter. An emitter can send out a
+// burst of particles when it is created, a steady stream of them while it
+// lives, or both.
+#[derive(Debug, Clone, Deserialize)]
+pub struct EmitterDefinition {
+    pub sprite: Sprite,
+    #[serde(default)]
+    pub layer: Layer,
+    // Particles per second
+    #[serde(default)]
+    pub rate: f32,
+    // Particles sent out at once when the emitter is created
+    #[serde(default)]
+    pub burst: u32,
+    // The middle of the cone particles are sent out in, in degrees where 0
+    // is to the right and 90 is down
+    #[serde(default)]
+    pub direction: f32,
+    // Width of the cone in degrees
+    #[serde(default = "full_circle")]
+    pub cone: f32,
+    // Pixels per second
+    pub speed: Range,
+    // Seconds
+    pub lifetime: Range,
+    #[serde(default = "white")]
+    pub color: Vec<ColorStop>,
+    #[serde(default = "full_size")]
+    pub size: Vec<SizeStop>,
+    // Pixels per second per second, downwards
+    #[serde(default)]
+    pub gravity: f32,
+    // Exponential slow down, see Drag
+    #[serde(default)]
+    pub drag: f32,
+}
+
+// Finds the two stops around t and blends between them
+fn interpolate<S, T>(
+    stops: &[S],
+    t: f32,
+    at: impl Fn(&S) -> f32,
+    mix: impl Fn(&S, &S, f32) -> T,
+) -> T {
+    let next = stops.iter().position(|s| at(s) > t).unwrap_or(stops.len());
+    let before = &stops[next.saturating_sub(1)];
+    let after = stops.get(next).unwrap_or(before);
+    let span = at(after) - at(before);
+    let amount = if span > 0.0 {
+        (t - at(before)) / span
+    } else {
+        0.0
+    };
+    mix(before, after, amount)
+}
+
+impl EmitterDefinition {
+    pub fn color_at(&self, t: f32) -> Color {
+        interpolate(&self.color, t, |s| s.at, |a, b, t| a.color.lerp(b.color, t))
+    }
+
+    pub fn scale_at(&self, t: f32) -> f32 {
+        interpolate(
+            &self.size,
+            t,
+            |s| s.at,
+            |a, b, t| a.scale + (b.scale - a.scale) * t,
+        )
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.color.is_empty() || self.size.is_empty() {
+            return Err("Needs at least one color and size".to_string());
+        }
+        let sorted = |times: Vec<f32>| times.windows(2).all(|pair| pair[0] <= pair[1]);
+        if !sorted(self.color.iter().map(|s| s.at).collect())
+            || !sorted(self.size.iter().map(|s| s.at).collect())
+        {
+            return Err("Stops must be sorted by time".to_string());
+        }
+        if self.speed.min > self.speed.max || self.lifetime.min > self.lifetime.max {
+            return Err("Ranges must have min below max".to_string());
+        }
+        if self.lifetime.min <= 0.0 || self.rate < 0.0 {
+            return Err("Lifetime must be positive and rate can't be negative".to_string());
+        }
+        Ok(())
+    }
+}
+
+// All the particle effects in the game, by name
+#[derive(Debug, Clone, Deserialize)]
+pub struct ParticleEffects {
+    pub emitters: HashMap<String, EmitterDefinition>,
+}
+
+impl ParticleEffects {
+    pub fn parse(json: &str) -> Result<Self, Box<dyn Error>> {
+        let effects: ParticleEffects = serde_json::from_str(json)?;
+        for (name, emitter) in &effects.emitters {
+            emitter
+                .validate()
+                .map_err(|e| format!("Invalid emitter {}: {}", name, e))?;
+        }
+        Ok(effects)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let json = std::fs::read_to_string(path)?;
+        ParticleEffects::parse(&json)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e).into())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&EmitterDefinition> {
+        self.emitters.get(name)
+    }
+}
+
+impl Default for ParticleEffects {
+    fn default() -> Self {
+        ParticleEffects::parse(include_str!("../../assets/particles.json"))
+            .expect("Bad built in particle effects")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn emitter(extra: &str) -> Result<ParticleEffects, Box<dyn Error>> {
+        ParticleEffects::parse(&format!(
+            r#"{{ "emitters": {{ "test": {{ "sprite": "Glow",
+                "speed": {{ "min": 10, "max": 20 }},
+                "lifetime": {{ "min": 1, "max": 1 }} {} }} }} }}"#,
+            extra
+        ))
+    }
+
+    #[test]
+    fn test_built_in_effects() {
+        let effects = ParticleEffects::default();
+        assert!(effects.get("alien_explosion").is_some());
+        assert!(effects.get("player_explosion").is_some());
+    }
+
+    #[test]
+    fn test_defaults() {
+        let effects = emitter("").unwrap();
+        let test = effects.get("test").unwrap();
+        assert_eq!(test.cone, 360.0);
+        assert_eq!(test.layer, Layer::Effects);
+        assert_eq!(test.color_at(0.7), Color::WHITE);
+        assert_eq!(test.scale_at(0.7), 1.0);
+    }
+
+    #[test]
+    fn test_interpolation() {
+        let effects = emitter(
+            r#", "color": [
+                { "at": 0, "color": { "r": 200, "g": 0, "b": 0 } },
+                { "at": 0.5, "color": { "r": 100, "g": 100, "b": 0, "a": 55 } } ],
+               "size": [ { "at": 0.5, "scale": 1 }, { "at": 1, "scale": 3 } ]"#,
+        )
+        .unwrap();
+        let test = effects.get("test").unwrap();
+        assert_eq!(test.color_at(0.0), Color::rgb(200, 0, 0));
+        assert_eq!(test.color_at(0.25), Color::rgba(150, 50, 0, 155));
+        assert_eq!(test.color_at(0.9), Color::rgba(100, 100, 0, 55));
+        assert_eq!(test.scale_at(0.2), 1.0);
+        assert_eq!(test.scale_at(0.75), 2.0);
+        assert_eq!(test.scale_at(1.0), 3.0);
+    }
+
+    #[test]
+    fn test_invalid_emitters() {
+        assert!(emitter(r#", "color": []"#).is_err());
+        assert!(
+            emitter(r#", "size": [ { "at": 1, "scale": 1 }, { "at": 0, "scale": 1 } ]"#).is_err()
+        );
+        assert!(emitter(r#", "rate": -1"#).is_err());
+    }
+}
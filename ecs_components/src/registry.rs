@@ -0,0 +1,117 @@
+// Every component the game uses, so the world can register them all and
+// tools can list how many entities have each one
+use specs::{Component, Join, World, WorldExt};
+
+use crate::{
+    Acceleration, Alpha, Animation, Blink, Drag, Draw, Emitter, FadeOut, HarmsAliens, HarmsPlayer,
+    Invincibility, IsAlien, IsExplosion, IsFast, IsPlayer, KeepInside, Layer, Lifetime, MaxSpeed,
+    MovementKind, Particle, Position, ReapWhenOutside, Rotation, RotationKind, Sprite, Text, Tiled,
+    Tint, Twinkle, Velocity, ZOrder,
+};
+
+struct ComponentInfo {
+    name: &'static str,
+    register: fn(&mut World),
+    count: fn(&World) -> usize,
+}
+
+fn register<T: Component>(world: &mut World)
+where
+    T::Storage: Default,
+{
+    world.register::<T>();
+}
+
+fn count<T: Component>(world: &World) -> usize {
+    (&world.read_storage::<T>()).join().count()
+}
+
+macro_rules! components {
+    ($($component:ident),* $(,)?) => {
+        &[$(ComponentInfo {
+            name: stringify!($component),
+            register: register::<$component>,
+            count: count::<$component>,
+        }),*]
+    };
+}
+
+const COMPONENTS: &[ComponentInfo] = components![
+    Acceleration,
+    Alpha,
+    Animation,
+    Blink,
+    Drag,
+    Draw,
+    Emitter,
+    FadeOut,
+    HarmsAliens,
+    HarmsPlayer,
+    Invincibility,
+    IsAlien,
+    IsExplosion,
+    IsFast,
+    IsPlayer,
+    KeepInside,
+    Layer,
+    Lifetime,
+    MaxSpeed,
+    MovementKind,
+    Particle,
+    Position,
+    ReapWhenOutside,
+    Rotation,
+    RotationKind,
+    Sprite,
+    Text,
+    Tiled,
+    Tint,
+    Twinkle,
+    Velocity,
+    ZOrder,
+];
+
+pub fn register_components(world: &mut World) {
+    for component in COMPONENTS {
+        (component.register)(world);
+    }
+}
+
+// The number of entities with each component
+pub fn component_counts(world: &World) -> Vec<(&'static str, usize)> {
+    COMPONENTS
+        .iter()
+        .map(|component| (component.name, (component.count)(world)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AlienKind;
+    use specs::Builder;
+
+    #[test]
+    fn test_component_counts() {
+        let mut world = World::new();
+        register_components(&mut world);
+        world
+            .create_entity()
+            .with(IsAlien {
+                kind: AlienKind::Ufo,
+            })
+            .with(Tiled)
+            .build();
+        world
+            .create_entity()
+            .with(IsAlien {
+                kind: AlienKind::Ufo,
+            })
+            .build();
+        let counts = component_counts(&world);
+        assert_eq!(counts.len(), COMPONENTS.len());
+        assert!(counts.contains(&("IsAlien", 2)));
+        assert!(counts.contains(&("Tiled", 1)));
+        assert!(counts.contains(&("Velocity", 0)));
+    }
+}
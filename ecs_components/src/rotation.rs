@@ -0,0 +1,12 @@
+use specs::{Component, VecStorage};
+use specs_derive::Component;
+
+// Clockwise rotation of the sprite in degrees. The sprite is rotated around
+// pivot, which is relative to the top left corner of the entity, or around
+// its center when there is no pivot.
+#[derive(Debug, Component, Copy, Clone, Default)]
+#[storage(VecStorage)]
+pub struct Rotation {
+    pub degrees: f32,
+    pub pivot: Option<geometry::Position>,
+}
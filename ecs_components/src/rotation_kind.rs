@@ -0,0 +1,13 @@
+use specs::{Component, VecStorage};
+use specs_derive::Component;
+
+// How the Rotation of an entity changes over time
+#[derive(Debug, Component)]
+#[storage(VecStorage)]
+pub enum RotationKind {
+    // Point along the velocity, for sprites drawn pointing right
+    AlignWithVelocity,
+    Spin { degrees_per_second: f32 },
+    // Tilt with the vertical speed, reaching max_degrees at full_speed
+    Bank { max_degrees: f32, full_speed: f32 },
+}
@@ -1,13 +1,14 @@
+use serde::Deserialize;
 use specs::{Component, VecStorage};
 use specs_derive::Component;
 
-#[derive(Debug, Component, PartialEq, PartialOrd, Eq, Ord)]
+#[derive(Debug, Component, Copy, Clone, PartialEq, PartialOrd, Eq, Ord, Hash, Deserialize)]
 #[storage(VecStorage)]
 pub enum Sprite {
     Player,
-    PlayerGhost,
     BasicShot,
     UFO,
     UFOShot,
     Glow,
+    Nebula,
 }
@@ -0,0 +1,120 @@
+use std::error::Error;
+use std::path::Path;
+
+use serde::Deserialize;
+use shared_types::Color;
+
+use crate::Sprite;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct StarLayer {
+    // Pixels per second towards the left
+    pub speed: f32,
+    // Stars per 100x100 pixels
+    pub density: f32,
+    pub min_radius: i16,
+    pub max_radius: i16,
+    pub color: Color,
+    // How much the stars dim when twinkling, from 0 to 1
+    #[serde(default)]
+    pub twinkle: f32,
+    // Average twinkle speed in radians per second
+    #[serde(default)]
+    pub twinkle_speed: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ImageLayer {
+    pub sprite: Sprite,
+    pub speed: f32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum StarfieldLayer {
+    Stars(StarLayer),
+    // A sprite repeated across the screen, like a nebula
+    Image(ImageLayer),
+}
+
+impl StarfieldLayer {
+    pub fn speed(&self) -> f32 {
+        match self {
+            StarfieldLayer::Stars(layer) => layer.speed,
+            StarfieldLayer::Image(layer) => layer.speed,
+        }
+    }
+}
+
+// The scrolling background. The layers are drawn in the order they are
+// listed, so the farthest layer, which should also be the slowest, is first.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Starfield {
+    pub layers: Vec<StarfieldLayer>,
+}
+
+impl Starfield {
+    pub fn parse(json: &str) -> Result<Self, Box<dyn Error>> {
+        let starfield: Starfield = serde_json::from_str(json)?;
+        for layer in &starfield.layers {
+            if let StarfieldLayer::Stars(stars) = layer {
+                if stars.min_radius > stars.max_radius || stars.density < 0.0 {
+                    return Err(format!("Invalid star layer: {:?}", stars).into());
+                }
+            }
+        }
+        Ok(starfield)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let json = std::fs::read_to_string(path)?;
+        Starfield::parse(&json)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e).into())
+    }
+}
+
+impl Default for Starfield {
+    fn default() -> Self {
+        Starfield::parse(include_str!("../../assets/starfield.json"))
+            .expect("Bad built in starfield")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_built_in_starfield() {
+        let starfield = Starfield::default();
+        assert!(!starfield.layers.is_empty());
+    }
+
+    #[test]
+    fn test_parse() {
+        let starfield = Starfield::parse(
+            r#"{ "layers": [
+                { "kind": "image", "sprite": "Nebula", "speed": 4 },
+                { "kind": "stars", "speed": 15, "density": 0.5, "min_radius": 1,
+                  "max_radius": 3, "color": { "r": 1, "g": 2, "b": 3 } } ] }"#,
+        )
+        .unwrap();
+        assert_eq!(starfield.layers.len(), 2);
+        match &starfield.layers[1] {
+            StarfieldLayer::Stars(stars) => {
+                assert_eq!(stars.color, Color::rgb(1, 2, 3));
+                assert_eq!(stars.twinkle, 0.0);
+            }
+            _ => panic!("Expected a star layer"),
+        }
+    }
+
+    #[test]
+    fn test_invalid_radius() {
+        let result = Starfield::parse(
+            r#"{ "layers": [ { "kind": "stars", "speed": 15, "density": 0.5,
+                "min_radius": 3, "max_radius": 1, "color": { "r": 1, "g": 2, "b": 3 } } ] }"#,
+        );
+        assert!(result.is_err());
+    }
+}
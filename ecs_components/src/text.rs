@@ -0,0 +1,12 @@
+use shared_types::Color;
+use specs::{Component, VecStorage};
+use specs_derive::Component;
+
+// Text drawn centered on the entity's position, like the score shown
+// where an alien was shot
+#[derive(Debug, Component, Clone)]
+#[storage(VecStorage)]
+pub struct Text {
+    pub text: String,
+    pub color: Color,
+}
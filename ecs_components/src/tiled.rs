@@ -0,0 +1,7 @@
+use specs::{Component, NullStorage};
+use specs_derive::Component;
+
+// The sprite is repeated horizontally to cover the whole width of the screen
+#[derive(Debug, Component, Default)]
+#[storage(NullStorage)]
+pub struct Tiled;
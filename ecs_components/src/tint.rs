@@ -0,0 +1,8 @@
+use shared_types::Color;
+use specs::{Component, VecStorage};
+use specs_derive::Component;
+
+// Multiplied into the colors of the entity's sprite
+#[derive(Debug, Component, Copy, Clone)]
+#[storage(VecStorage)]
+pub struct Tint(pub Color);
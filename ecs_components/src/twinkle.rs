@@ -0,0 +1,19 @@
+use specs::{Component, VecStorage};
+use specs_derive::Component;
+
+// Makes the brightness of a star go up and down. At the darkest the
+// brightness is reduced by amplitude, which is between 0 and 1.
+#[derive(Debug, Component, Copy, Clone)]
+#[storage(VecStorage)]
+pub struct Twinkle {
+    pub amplitude: f32,
+    // Radians per second
+    pub speed: f32,
+    pub phase: f32,
+}
+
+impl Twinkle {
+    pub fn brightness(&self) -> f32 {
+        1.0 - self.amplitude * (0.5 + 0.5 * self.phase.sin())
+    }
+}
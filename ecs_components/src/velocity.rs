@@ -1,3 +1,4 @@
+use geometry::Vec2;
 use specs::{Component, VecStorage};
 use specs_derive::Component;
 
@@ -7,3 +8,20 @@ pub struct Velocity {
     pub x: f32,
     pub y: f32,
 }
+
+impl Velocity {
+    pub fn vec2(&self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+
+    pub fn set(&mut self, vec: Vec2) {
+        self.x = vec.x;
+        self.y = vec.y;
+    }
+}
+
+impl From<Vec2> for Velocity {
+    fn from(vec: Vec2) -> Self {
+        Velocity { x: vec.x, y: vec.y }
+    }
+}
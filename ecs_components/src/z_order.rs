@@ -0,0 +1,9 @@
+use specs::{Component, VecStorage};
+use specs_derive::Component;
+
+// Orders the entities within a layer, higher values are drawn on top.
+// Entities without a ZOrder have the value 0, and entities with the same
+// value are drawn in the order of their entity ids, so they don't flicker.
+#[derive(Debug, Component, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[storage(VecStorage)]
+pub struct ZOrder(pub i32);
@@ -1,49 +1,64 @@
 use std::error::Error;
 
 use ecs_systems::AlienShooting;
+use ecs_systems::Blinking;
 use ecs_systems::CollisionChecker;
 use ecs_systems::EnemySpawning;
 use ecs_systems::ForceInside;
 use ecs_systems::InvincibilityWatching;
 use ecs_systems::LifetimeWatching;
 use ecs_systems::NonPlayerControl;
+use ecs_systems::ParticleEmitting;
 use ecs_systems::PlayerControl;
 use ecs_systems::PlayerShooting;
 use ecs_systems::ReapOutsiders;
-use ecs_systems::SpawnerSpawning;
 use ecs_systems::StarSpawner;
+use ecs_systems::StarfieldFilling;
+use ecs_systems::StatsTracking;
+use ecs_systems::Timed;
+use ecs_systems::Twinkling;
+use ecs_systems::UpdateAnimation;
+use ecs_systems::UpdateCamera;
+use ecs_systems::UpdateParticles;
 use ecs_systems::UpdatePos;
+use ecs_systems::UpdateRotation;
 
-use ecs_components::{
-    Draw, HarmsAliens, HarmsPlayer, Invincibility, IsAlien, IsExplosion, IsPlayer, KeepInside,
-    Lifetime, MovementKind, Position, ReapWhenOutside, SpawnerKind, Sprite, Velocity,
+use ecs_components::{ParticleEffects, Starfield};
+use localization::Strings;
+use shared_types::{
+    Accessibility, AudioQueue, Camera, Difficulty, ElapsedSeconds, EntitySizes, PlayingGameState,
+    RunStats, SystemTimes,
 };
-use shared_types::{ElapsedSeconds, EntitySizes, PlayingGameState};
 use specs::world::WorldExt;
-use specs::{Dispatcher, DispatcherBuilder, RunNow, World};
+use specs::{Dispatcher, DispatcherBuilder, RunNow, System, World};
 
-pub fn setup<'a>(entity_sizes: EntitySizes) -> Result<(World, Dispatcher<'a, 'a>), Box<dyn Error>> {
+// Acceleration of the player ship. Together with the drag and max speed
+// given to the ship when it is created, this decides how it handles.
+const PLAYER_THRUST: f32 = 3000.0;
+
+pub fn setup<'a>(
+    entity_sizes: EntitySizes,
+    starfield: Starfield,
+    particle_effects: ParticleEffects,
+) -> Result<(World, Dispatcher<'a, 'a>), Box<dyn Error>> {
     let mut world = World::new();
 
-    world.register::<Draw>();
-    world.register::<HarmsAliens>();
-    world.register::<HarmsPlayer>();
-    world.register::<Invincibility>();
-    world.register::<IsAlien>();
-    world.register::<IsExplosion>();
-    world.register::<IsPlayer>();
-    world.register::<KeepInside>();
-    world.register::<Lifetime>();
-    world.register::<MovementKind>();
-    world.register::<Position>();
-    world.register::<ReapWhenOutside>();
-    world.register::<SpawnerKind>();
-    world.register::<Sprite>();
-    world.register::<Velocity>();
+    ecs_components::register_components(&mut world);
+
+    world.insert(starfield);
+    world.insert(particle_effects);
+    world.insert(AudioQueue::default());
+    world.insert(ElapsedSeconds::default());
+    world.insert(Difficulty::default());
+    world.insert(Accessibility::default());
+    world.insert(RunStats::default());
+    world.insert(Strings::default());
+    let system_times = SystemTimes::default();
+    world.insert(system_times.clone());
 
-    let dispatcher = DispatcherBuilder::new()
+    let dispatcher = TimedBuilder::new(system_times)
         .with(NonPlayerControl, "NonPlayerControl", &[])
-        .with(PlayerControl, "PlayerControl", &[])
+        .with(PlayerControl::new(PLAYER_THRUST), "PlayerControl", &[])
         .with(
             PlayerShooting::new(entity_sizes.basic_shot_size),
             "PlayerShooting",
@@ -59,36 +74,65 @@ pub fn setup<'a>(entity_sizes: EntitySizes) -> Result<(World, Dispatcher<'a, 'a>
             "UpdatePos",
             &["NonPlayerControl", "PlayerControl"],
         )
+        .with(UpdateRotation, "UpdateRotation", &["UpdatePos"])
         .with(ReapOutsiders, "ReapOutsiders", &["UpdatePos"])
         .with(ForceInside, "ForceInside", &["UpdatePos"])
         .with(CollisionChecker, "CollisionChecker", &["ForceInside"])
+        .with(UpdateCamera, "UpdateCamera", &["CollisionChecker"])
         .with(
             EnemySpawning::new(entity_sizes.ufo_size.into()),
             "EnemySpawning",
             &[],
         )
-        .with(SpawnerSpawning, "SpawnerSpawning", &[])
+        .with(ParticleEmitting, "ParticleEmitting", &[])
+        .with(UpdateParticles, "UpdateParticles", &[])
         .with(LifetimeWatching, "LifetimeWatching", &[])
         .with(InvincibilityWatching, "InvincibilityWatcher", &[])
         .with(StarSpawner, "StarSpawner", &[])
+        .with(Twinkling, "Twinkling", &[])
+        .with(Blinking, "Blinking", &[])
+        .with(UpdateAnimation, "UpdateAnimation", &[])
+        .with(StatsTracking, "StatsTracking", &[])
         .build();
 
     Ok((world, dispatcher))
 }
 
+// Builds a dispatcher where every system records how long it runs
+struct TimedBuilder<'a, 'b> {
+    builder: DispatcherBuilder<'a, 'b>,
+    times: SystemTimes,
+}
+
+impl<'a, 'b> TimedBuilder<'a, 'b> {
+    fn new(times: SystemTimes) -> Self {
+        TimedBuilder {
+            builder: DispatcherBuilder::new(),
+            times,
+        }
+    }
+
+    fn with<S>(self, system: S, name: &'static str, dependencies: &[&str]) -> Self
+    where
+        S: for<'c> System<'c> + Send + 'a,
+    {
+        let timed = Timed::new(system, name, self.times.clone());
+        TimedBuilder {
+            builder: self.builder.with(timed, name, dependencies),
+            times: self.times,
+        }
+    }
+
+    fn build(self) -> Dispatcher<'a, 'b> {
+        self.builder.build()
+    }
+}
+
 pub fn initialize_world(world: &mut World) {
     world.delete_all();
+    world.write_resource::<Camera>().reset();
 
-    // Add initial stars. The elapsed time of 0.016
-    // is close to what we get when the game is running.
-    // 3000 iterations is enough to get stars all over
-    // the screen (found by fiddling)
-    let mut star_spawner = StarSpawner;
-    let mut update_position = UpdatePos;
-    for _ in 0..3000 {
-        world.insert(ElapsedSeconds(0.016));
-        world.insert(PlayingGameState::new());
-        star_spawner.run_now(world);
-        update_position.run_now(world);
-    }
+    world.insert(PlayingGameState::new());
+    world.insert(RunStats::default());
+    StarfieldFilling.run_now(world);
 }
@@ -1,7 +1,11 @@
-use ecs_components::{HarmsPlayer, IsAlien, IsPlayer, Position, ReapWhenOutside, Sprite, Velocity};
+use ecs_components::{
+    HarmsPlayer, IsAlien, IsPlayer, Layer, Position, ReapWhenOutside, Rotation, RotationKind,
+    Sprite, Velocity,
+};
 use geometry::{Rect, RectSize};
 use rand::Rng;
-use specs::{Entities, ReadStorage, System, WriteStorage};
+use shared_types::{AudioQueue, SoundEffect};
+use specs::{Entities, ReadStorage, System, Write, WriteStorage};
 
 pub struct AlienShooting {
     shot_size: RectSize,
@@ -16,9 +20,13 @@ impl AlienShooting {
 impl<'a> System<'a> for AlienShooting {
     type SystemData = (
         Entities<'a>,
+        Write<'a, AudioQueue>,
         WriteStorage<'a, Position>,
         WriteStorage<'a, Velocity>,
         WriteStorage<'a, Sprite>,
+        WriteStorage<'a, Layer>,
+        WriteStorage<'a, Rotation>,
+        WriteStorage<'a, RotationKind>,
         WriteStorage<'a, ReapWhenOutside>,
         WriteStorage<'a, HarmsPlayer>,
         ReadStorage<'a, IsAlien>,
@@ -29,9 +37,13 @@ impl<'a> System<'a> for AlienShooting {
         &mut self,
         (
             entities,
+            mut sounds,
             mut position,
             mut velocity,
             mut sprite,
+            mut layer,
+            mut rotation,
+            mut rotation_kind,
             mut reap_when_outside,
             mut harms_player,
             is_alien,
@@ -52,14 +64,10 @@ impl<'a> System<'a> for AlienShooting {
 
         if let Some(player_pos) = player_pos {
             for pos in fire_positions {
-                let diff = (
-                    player_pos.rect.center().0 - pos.rect.center().0,
-                    player_pos.rect.center().1 - pos.rect.center().1,
-                );
-                let diff_size = (diff.0.powf(2.0) + diff.1.powf(2.0)).sqrt();
                 let speed = 500.0;
-                let vel = (speed * diff.0 / diff_size, speed * diff.1 / diff_size);
+                let vel = (player_pos.rect.center() - pos.rect.center()).with_length(speed);
                 let shot_pos = pos.rect.center();
+                sounds.play_at(SoundEffect::AlienShot, shot_pos);
                 entities
                     .build_entity()
                     .with(
@@ -68,8 +76,17 @@ impl<'a> System<'a> for AlienShooting {
                         },
                         &mut position,
                     )
-                    .with(Velocity { x: vel.0, y: vel.1 }, &mut velocity)
+                    .with(Velocity::from(vel), &mut velocity)
                     .with(Sprite::UFOShot, &mut sprite)
+                    .with(Layer::Projectiles, &mut layer)
+                    .with(
+                        Rotation {
+                            degrees: vel.angle().to_degrees(),
+                            pivot: None,
+                        },
+                        &mut rotation,
+                    )
+                    .with(RotationKind::AlignWithVelocity, &mut rotation_kind)
                     .with(ReapWhenOutside, &mut reap_when_outside)
                     .with(HarmsPlayer, &mut harms_player)
                     .build();
@@ -0,0 +1,16 @@
+use ecs_components::Blink;
+use shared_types::ElapsedSeconds;
+use specs::{Read, System, WriteStorage};
+
+pub struct Blinking;
+
+impl<'a> System<'a> for Blinking {
+    type SystemData = (Read<'a, ElapsedSeconds>, WriteStorage<'a, Blink>);
+
+    fn run(&mut self, (delta_time, mut blink): Self::SystemData) {
+        use specs::Join;
+        for blink in (&mut blink).join() {
+            blink.elapsed = (blink.elapsed + delta_time.0) % (2.0 * blink.interval);
+        }
+    }
+}
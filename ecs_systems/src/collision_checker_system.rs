@@ -1,20 +1,97 @@
 use ecs_components::{
-    HarmsAliens, HarmsPlayer, Invincibility, IsAlien, IsExplosion, IsPlayer, Lifetime, Position,
-    SpawnerKind,
+    Emitter, FadeOut, HarmsAliens, HarmsPlayer, Invincibility, IsAlien, IsExplosion, IsFast,
+    IsPlayer, Layer, Lifetime, Position, Text, Velocity,
+};
+use geometry::{Rect, Vec2};
+use localization::Strings;
+use shared_types::{
+    AudioQueue, Camera, Color, ElapsedSeconds, PlayingGameState, RunStats, SoundEffect,
 };
-use geometry::Rect;
-use shared_types::PlayingGameState;
 
-use specs::{Entities, ReadStorage, System, Write, WriteStorage};
+use specs::{
+    BitSet, Builder, Entities, Entity, LazyUpdate, Read, ReadStorage, System, Write, WriteStorage,
+};
 
 pub struct CollisionChecker;
 
+const ALIEN_SCORE: u32 = 1;
+
+// Screen shake from each alien explosion, and the most added in one frame
+// so several kills at once don't shake as hard as dying
+const ALIEN_EXPLOSION_TRAUMA: f32 = 0.15;
+const MAX_EXPLOSION_TRAUMA: f32 = 0.4;
+const PLAYER_DEATH_TRAUMA: f32 = 0.8;
+
+// Popups rise from where an alien was shot and fade away
+const POPUP_SPEED: f32 = 60.0;
+const POPUP_SECONDS: f32 = 0.8;
+const POPUP_FADE_SECONDS: f32 = 0.5;
+const SCORE_COLOR: Color = Color::rgb(255, 255, 160);
+
+fn spawn_popup(
+    entities: &Entities,
+    updater: &LazyUpdate,
+    center: geometry::Position,
+    text: String,
+    color: Color,
+) {
+    updater
+        .create_entity(entities)
+        .with(Position {
+            rect: Rect::new(center, (0, 0).into()),
+        })
+        .with(Velocity {
+            x: 0.0,
+            y: -POPUP_SPEED,
+        })
+        .with(Text { text, color })
+        .with(Lifetime {
+            seconds: POPUP_SECONDS,
+        })
+        .with(FadeOut {
+            seconds: POPUP_FADE_SECONDS,
+        })
+        .with(Layer::Hud)
+        .build();
+}
+
+struct Mover<'s> {
+    velocity: &'s ReadStorage<'s, Velocity>,
+    is_fast: &'s ReadStorage<'s, IsFast>,
+    delta_time: f32,
+}
+
+impl<'s> Mover<'s> {
+    fn movement(&self, entity: Entity) -> Vec2 {
+        self.velocity
+            .get(entity)
+            .map_or(Vec2::ZERO, |v| v.vec2() * self.delta_time)
+    }
+
+    // Fast entities are checked along the path they moved during the last
+    // frame, relative to the target, so they can't skip past it.
+    fn hits(&self, harmer: (Entity, &Position), target: (Entity, &Position)) -> bool {
+        if !self.is_fast.contains(harmer.0) {
+            return target.1.rect.overlaps(&harmer.1.rect);
+        }
+        let relative = self.movement(harmer.0) - self.movement(target.0);
+        let previous = harmer.1.rect.translated(-relative);
+        previous.sweep_overlaps(relative, &target.1.rect)
+    }
+}
+
 impl<'a> System<'a> for CollisionChecker {
     type SystemData = (
         Entities<'a>,
+        Read<'a, LazyUpdate>,
+        Read<'a, Strings>,
         Write<'a, PlayingGameState>,
+        Read<'a, ElapsedSeconds>,
+        Write<'a, Camera>,
+        Write<'a, AudioQueue>,
+        Write<'a, RunStats>,
         WriteStorage<'a, Position>,
-        WriteStorage<'a, SpawnerKind>,
+        WriteStorage<'a, Emitter>,
         WriteStorage<'a, Lifetime>,
         WriteStorage<'a, IsExplosion>,
         ReadStorage<'a, IsAlien>,
@@ -22,15 +99,23 @@ impl<'a> System<'a> for CollisionChecker {
         ReadStorage<'a, IsPlayer>,
         ReadStorage<'a, HarmsPlayer>,
         ReadStorage<'a, Invincibility>,
+        ReadStorage<'a, Velocity>,
+        ReadStorage<'a, IsFast>,
     );
 
     fn run(
         &mut self,
         (
             entities,
+            updater,
+            strings,
             mut active_game_state,
+            delta_time,
+            mut camera,
+            mut sounds,
+            mut stats,
             mut position,
-            mut spawner_kind,
+            mut emitter,
             mut lifetime,
             mut is_explosion,
             is_alien,
@@ -38,22 +123,49 @@ impl<'a> System<'a> for CollisionChecker {
             is_player,
             harms_player,
             invincibility,
+            velocity,
+            is_fast,
         ): Self::SystemData,
     ) {
         use specs::Join;
+        let mover = Mover {
+            velocity: &velocity,
+            is_fast: &is_fast,
+            delta_time: delta_time.0,
+        };
         let mut explosion_positions = vec![];
+        let mut popup_positions = vec![];
+        // Deleted entities are still joined until the end of the frame, so
+        // each shot and alien is only counted in its first hit
+        let mut hit = BitSet::new();
         for (harmer_ent, harmer_pos, _) in (&entities, &position, &harms_aliens).join() {
-            for (alient_ent, alien_pos, _) in (&entities, &position, &is_alien).join() {
-                if alien_pos.rect.overlaps(&harmer_pos.rect) {
+            for (alient_ent, alien_pos, alien) in (&entities, &position, &is_alien).join() {
+                if hit.contains(harmer_ent.id()) || hit.contains(alient_ent.id()) {
+                    continue;
+                }
+                if mover.hits((harmer_ent, harmer_pos), (alient_ent, alien_pos)) {
+                    hit.add(harmer_ent.id());
+                    hit.add(alient_ent.id());
                     let _res = entities.delete(alient_ent);
                     let _res = entities.delete(harmer_ent);
                     explosion_positions.push((alien_pos.rect.left(), alien_pos.rect.top()));
-                    active_game_state.score += 1;
+                    popup_positions.push(alien_pos.rect.center());
+                    active_game_state.score += ALIEN_SCORE;
+                    stats.hits += 1;
+                    stats.add_kill(alien.kind.id());
                 }
             }
         }
 
+        for center in popup_positions {
+            let score = format!("+{}", strings.number(ALIEN_SCORE.into()));
+            spawn_popup(&entities, &updater, center, score, SCORE_COLOR);
+        }
+
+        let explosion_trauma = ALIEN_EXPLOSION_TRAUMA * explosion_positions.len() as f32;
+        camera.add_trauma(explosion_trauma.min(MAX_EXPLOSION_TRAUMA));
         for explosion_position in explosion_positions {
+            sounds.play_at(SoundEffect::Explosion, explosion_position.into());
             entities
                 .build_entity()
                 .with(
@@ -62,7 +174,7 @@ impl<'a> System<'a> for CollisionChecker {
                     },
                     &mut position,
                 )
-                .with(SpawnerKind::Fire(0.2), &mut spawner_kind)
+                .with(Emitter::new("alien_explosion"), &mut emitter)
                 .with(Lifetime { seconds: 0.1 }, &mut lifetime)
                 .with(IsExplosion, &mut is_explosion)
                 .build();
@@ -73,7 +185,7 @@ impl<'a> System<'a> for CollisionChecker {
             for (player_ent, player_pos, _, _) in
                 (&entities, &position, &is_player, !&invincibility).join()
             {
-                if player_pos.rect.overlaps(&harmer_pos.rect) {
+                if mover.hits((harmer_ent, harmer_pos), (player_ent, player_pos)) {
                     let _res = entities.delete(harmer_ent);
                     let _res = entities.delete(player_ent);
                     dead_player_position = Some((player_pos.rect.left(), player_pos.rect.top()));
@@ -81,6 +193,9 @@ impl<'a> System<'a> for CollisionChecker {
             }
         }
         if let Some(dead_player_position) = dead_player_position {
+            camera.add_trauma(PLAYER_DEATH_TRAUMA);
+            stats.deaths += 1;
+            sounds.play_at(SoundEffect::PlayerDeath, dead_player_position.into());
             entities
                 .build_entity()
                 .with(
@@ -89,10 +204,124 @@ impl<'a> System<'a> for CollisionChecker {
                     },
                     &mut position,
                 )
-                .with(SpawnerKind::Fire(0.5), &mut spawner_kind)
+                .with(Emitter::new("player_explosion"), &mut emitter)
                 .with(Lifetime { seconds: 0.5 }, &mut lifetime)
                 .with(IsExplosion, &mut is_explosion)
                 .build();
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ecs_components::AlienKind;
+    use specs::{Dispatcher, DispatcherBuilder, Join, World, WorldExt};
+
+    fn world_with_checker() -> (World, Dispatcher<'static, 'static>) {
+        let mut world = World::new();
+        ecs_components::register_components(&mut world);
+        let mut dispatcher = DispatcherBuilder::new()
+            .with(CollisionChecker, "collision_checker", &[])
+            .build();
+        dispatcher.setup(&mut world);
+        (world, dispatcher)
+    }
+
+    fn add_at(world: &mut World, x: f32, harms_aliens: bool) {
+        let builder = world.create_entity().with(Position {
+            rect: Rect::new((x, 100.0).into(), (20, 20).into()),
+        });
+        if harms_aliens {
+            builder.with(HarmsAliens).build();
+        } else {
+            builder
+                .with(IsAlien {
+                    kind: AlienKind::Ufo,
+                })
+                .build();
+        }
+    }
+
+    #[test]
+    fn test_each_shot_and_alien_counts_once() {
+        let (mut world, mut dispatcher) = world_with_checker();
+        // One shot over two aliens
+        add_at(&mut world, 100.0, true);
+        add_at(&mut world, 100.0, false);
+        add_at(&mut world, 105.0, false);
+        // Two shots over one alien
+        add_at(&mut world, 300.0, true);
+        add_at(&mut world, 305.0, true);
+        add_at(&mut world, 300.0, false);
+
+        dispatcher.dispatch(&world);
+        world.maintain();
+
+        let stats = world.read_resource::<RunStats>();
+        assert_eq!(stats.hits, 2);
+        assert_eq!(stats.aliens_killed["ufo"], 2);
+        assert_eq!(world.read_resource::<PlayingGameState>().score, 2);
+        let aliens_left = (&world.entities(), &world.read_storage::<IsAlien>())
+            .join()
+            .count();
+        assert_eq!(aliens_left, 1);
+    }
+
+    // A shot at 1500 px/s over a long frame, which started before the alien
+    // at x=100 and has ended up past it
+    fn shot_past_alien(is_fast: bool) -> World {
+        let (mut world, mut dispatcher) = world_with_checker();
+        world.insert(ElapsedSeconds(0.1));
+        add_at(&mut world, 100.0, false);
+        let shot = world
+            .create_entity()
+            .with(Position {
+                rect: Rect::new((190.0, 100.0).into(), (20, 20).into()),
+            })
+            .with(Velocity { x: 1500.0, y: 0.0 })
+            .with(HarmsAliens);
+        if is_fast {
+            shot.with(IsFast).build();
+        } else {
+            shot.build();
+        }
+
+        dispatcher.dispatch(&world);
+        world.maintain();
+        world
+    }
+
+    #[test]
+    fn test_fast_shot_hits_alien_it_passed() {
+        let world = shot_past_alien(true);
+        assert_eq!(world.read_resource::<RunStats>().hits, 1);
+    }
+
+    #[test]
+    fn test_slow_shot_misses_alien_it_passed() {
+        let world = shot_past_alien(false);
+        assert_eq!(world.read_resource::<RunStats>().hits, 0);
+    }
+
+    #[test]
+    fn test_kills_show_score_popups() {
+        let (mut world, mut dispatcher) = world_with_checker();
+        add_at(&mut world, 100.0, true);
+        add_at(&mut world, 100.0, false);
+
+        dispatcher.dispatch(&world);
+        world.maintain();
+
+        let popups: Vec<_> = (
+            &world.read_storage::<Text>(),
+            &world.read_storage::<Lifetime>(),
+            &world.read_storage::<FadeOut>(),
+        )
+            .join()
+            .map(|(text, _, _)| text.text.clone())
+            .collect();
+        assert_eq!(popups, vec!["+1"]);
+        assert_eq!(world.read_resource::<PlayingGameState>().score, 1);
+    }
+}
@@ -1,9 +1,17 @@
-use ecs_components::{IsAlien, MovementKind, Position, Sprite, Velocity};
+use ecs_components::{
+    AlienKind, IsAlien, Layer, MovementKind, Position, Rotation, RotationKind, Sprite, Velocity,
+};
 use geometry::{Rect, RectSize};
 use rand::Rng;
-use shared_types::Arena;
+use shared_types::{Arena, Difficulty};
 use specs::{Entities, Read, System, WriteStorage};
 
+// The chance of an enemy appearing each frame at normal difficulty
+const ENEMY_CHANCE: f64 = 0.0099;
+
+// The fastest a UFO spins, either way round
+const MAX_SPIN: f32 = 90.0;
+
 pub struct EnemySpawning {
     ufo_size: RectSize,
 }
@@ -18,10 +26,14 @@ impl<'a> System<'a> for EnemySpawning {
     type SystemData = (
         Entities<'a>,
         Read<'a, Arena>,
+        Read<'a, Difficulty>,
         WriteStorage<'a, Position>,
         WriteStorage<'a, Velocity>,
         WriteStorage<'a, MovementKind>,
+        WriteStorage<'a, Rotation>,
+        WriteStorage<'a, RotationKind>,
         WriteStorage<'a, Sprite>,
+        WriteStorage<'a, Layer>,
         WriteStorage<'a, IsAlien>,
     );
 
@@ -30,16 +42,20 @@ impl<'a> System<'a> for EnemySpawning {
         (
             entities,
             arena,
+            difficulty,
             mut position,
             mut velocity,
             mut movement_kind,
+            mut rotation,
+            mut rotation_kind,
             mut sprite,
+            mut layer,
             mut is_alien,
         ): Self::SystemData,
     ) {
         let arena_rect = arena.0;
         let mut rng = rand::thread_rng();
-        if rng.gen_range(0..10000) > 9900 {
+        if rng.gen_bool(ENEMY_CHANCE * difficulty.enemy_rate() as f64) {
             let x = arena_rect.right() + 100.0;
             let y = rng.gen_range(arena_rect.top()..(arena_rect.bottom() - self.ufo_size.1));
             let pos = (x, y).into();
@@ -59,8 +75,21 @@ impl<'a> System<'a> for EnemySpawning {
                     &mut velocity,
                 )
                 .with(MovementKind::SideToSide, &mut movement_kind)
+                .with(Rotation::default(), &mut rotation)
+                .with(
+                    RotationKind::Spin {
+                        degrees_per_second: rng.gen_range(-MAX_SPIN..MAX_SPIN),
+                    },
+                    &mut rotation_kind,
+                )
                 .with(Sprite::UFO, &mut sprite)
-                .with(IsAlien, &mut is_alien)
+                .with(Layer::Enemies, &mut layer)
+                .with(
+                    IsAlien {
+                        kind: AlienKind::Ufo,
+                    },
+                    &mut is_alien,
+                )
                 .build();
         }
     }
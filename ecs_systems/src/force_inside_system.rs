@@ -1,4 +1,4 @@
-use ecs_components::{KeepInside, Position};
+use ecs_components::{KeepInside, Position, Velocity};
 use shared_types::Arena;
 use specs::{Read, ReadStorage, System, WriteStorage};
 
@@ -8,26 +8,25 @@ impl<'a> System<'a> for ForceInside {
     type SystemData = (
         Read<'a, Arena>,
         WriteStorage<'a, Position>,
+        WriteStorage<'a, Velocity>,
         ReadStorage<'a, KeepInside>,
     );
 
-    fn run(&mut self, (arena, mut position, keep_inside): Self::SystemData) {
+    fn run(&mut self, (arena, mut position, mut velocity, keep_inside): Self::SystemData) {
         use specs::Join;
-        for (pos, _) in (&mut position, &keep_inside).join() {
-            if pos.rect.left() < arena.0.left() {
-                pos.rect.set_left(arena.0.left());
-            }
-
-            if pos.rect.top() < arena.0.top() {
-                pos.rect.set_top(arena.0.top());
-            }
-
-            if pos.rect.right() > arena.0.right() {
-                pos.rect.set_right(arena.0.right());
-            }
+        for (pos, vel, _) in (&mut position, (&mut velocity).maybe(), &keep_inside).join() {
+            let before = pos.rect.topleft();
+            pos.rect.clamp_inside(&arena.0);
+            let after = pos.rect.topleft();
 
-            if pos.rect.bottom() > arena.0.bottom() {
-                pos.rect.set_bottom(arena.0.bottom())
+            // Don't let speed build up while pushing against the edge
+            if let Some(vel) = vel {
+                if after.0 != before.0 {
+                    vel.x = 0.0;
+                }
+                if after.1 != before.1 {
+                    vel.y = 0.0;
+                }
             }
         }
     }
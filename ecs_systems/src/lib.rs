@@ -1,27 +1,45 @@
 mod alien_shooting_system;
+mod blinking_system;
 mod collision_checker_system;
 mod enemy_spawning_system;
 mod force_inside_system;
 mod invincibility_watching_system;
 mod lifetime_watching_system;
 mod non_player_control_system;
+mod particle_emitting_system;
 mod player_control_system;
 mod player_shooting_system;
 mod reap_outsiders_system;
-mod spawner_spawning_system;
 mod star_spawner_system;
+mod starfield_filling_system;
+mod stats_tracking_system;
+mod timed_system;
+mod twinkling_system;
+mod update_animation_system;
+mod update_camera_system;
+mod update_particles_system;
 mod update_pos_system;
+mod update_rotation_system;
 
 pub use alien_shooting_system::AlienShooting;
+pub use blinking_system::Blinking;
 pub use collision_checker_system::CollisionChecker;
 pub use enemy_spawning_system::EnemySpawning;
 pub use force_inside_system::ForceInside;
 pub use invincibility_watching_system::InvincibilityWatching;
 pub use lifetime_watching_system::LifetimeWatching;
 pub use non_player_control_system::NonPlayerControl;
+pub use particle_emitting_system::ParticleEmitting;
 pub use player_control_system::PlayerControl;
 pub use player_shooting_system::PlayerShooting;
 pub use reap_outsiders_system::ReapOutsiders;
-pub use spawner_spawning_system::SpawnerSpawning;
 pub use star_spawner_system::StarSpawner;
+pub use starfield_filling_system::StarfieldFilling;
+pub use stats_tracking_system::StatsTracking;
+pub use timed_system::Timed;
+pub use twinkling_system::Twinkling;
+pub use update_animation_system::UpdateAnimation;
+pub use update_camera_system::UpdateCamera;
+pub use update_particles_system::UpdateParticles;
 pub use update_pos_system::UpdatePos;
+pub use update_rotation_system::UpdateRotation;
@@ -0,0 +1,117 @@
+use ecs_components::{
+    Acceleration, Drag, Emitter, IsExplosion, Layer, Particle, ParticleEffects, Position, Sprite,
+    Velocity,
+};
+use geometry::{Rect, Vec2};
+use rand::Rng;
+use shared_types::ElapsedSeconds;
+use specs::{Entities, Read, System, WriteStorage};
+
+pub struct ParticleEmitting;
+
+impl<'a> System<'a> for ParticleEmitting {
+    type SystemData = (
+        Entities<'a>,
+        Read<'a, ElapsedSeconds>,
+        Read<'a, ParticleEffects>,
+        WriteStorage<'a, Emitter>,
+        WriteStorage<'a, Particle>,
+        WriteStorage<'a, Position>,
+        WriteStorage<'a, Velocity>,
+        WriteStorage<'a, Acceleration>,
+        WriteStorage<'a, Drag>,
+        WriteStorage<'a, Sprite>,
+        WriteStorage<'a, Layer>,
+        WriteStorage<'a, IsExplosion>,
+    );
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            delta_time,
+            effects,
+            mut emitter,
+            mut particle,
+            mut position,
+            mut velocity,
+            mut acceleration,
+            mut drag,
+            mut sprite,
+            mut layer,
+            mut is_explosion,
+        ): Self::SystemData,
+    ) {
+        use specs::Join;
+        let mut rng = rand::thread_rng();
+        let mut spawns = vec![];
+        for (entity, emitter, pos) in (&entities, &mut emitter, &position).join() {
+            let definition = match effects.get(&emitter.effect) {
+                Some(definition) => definition,
+                None => continue,
+            };
+            emitter.pending += definition.rate * delta_time.0;
+            let mut count = emitter.pending.floor();
+            emitter.pending -= count;
+            if !emitter.burst_done {
+                emitter.burst_done = true;
+                count += definition.burst as f32;
+            }
+            let explosion = is_explosion.contains(entity);
+            for _ in 0..count as usize {
+                spawns.push((&emitter.effect, definition, pos.rect.center(), explosion));
+            }
+        }
+
+        for (effect, definition, center, explosion) in spawns {
+            let direction = definition.direction + rng.gen_range(-0.5..=0.5) * definition.cone;
+            let speed = definition.speed.sample(&mut rng);
+            let lifetime = definition.lifetime.sample(&mut rng);
+            let mut builder = entities
+                .build_entity()
+                .with(definition.sprite, &mut sprite)
+                .with(definition.layer, &mut layer)
+                .with(
+                    Position {
+                        rect: Rect::new(center, (1, 1).into()),
+                    },
+                    &mut position,
+                )
+                .with(
+                    Velocity::from(Vec2::from_angle(direction.to_radians()) * speed),
+                    &mut velocity,
+                )
+                .with(
+                    Particle {
+                        effect: effect.clone(),
+                        age: 0.0,
+                        lifetime,
+                        color: definition.color_at(0.0),
+                        scale: definition.scale_at(0.0),
+                    },
+                    &mut particle,
+                );
+            if definition.gravity != 0.0 {
+                builder = builder.with(
+                    Acceleration {
+                        x: 0.0,
+                        y: definition.gravity,
+                    },
+                    &mut acceleration,
+                );
+            }
+            if definition.drag > 0.0 {
+                builder = builder.with(
+                    Drag {
+                        rate: definition.drag,
+                    },
+                    &mut drag,
+                );
+            }
+            if explosion {
+                builder = builder.with(IsExplosion, &mut is_explosion);
+            }
+            builder.build();
+        }
+    }
+}
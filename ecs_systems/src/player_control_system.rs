@@ -1,36 +1,42 @@
-use ecs_components::{IsPlayer, Velocity};
+use ecs_components::{Acceleration, IsPlayer};
 use sdl_input::ControlState;
 use specs::{Read, ReadStorage, System, WriteStorage};
 
-pub struct PlayerControl;
+pub struct PlayerControl {
+    thrust: f32,
+}
+
+impl PlayerControl {
+    // thrust is the acceleration of the player ship, in pixels per second
+    // squared, while a direction is held
+    pub fn new(thrust: f32) -> Self {
+        PlayerControl { thrust }
+    }
+}
 
 impl<'a> System<'a> for PlayerControl {
     type SystemData = (
         Read<'a, ControlState>,
         ReadStorage<'a, IsPlayer>,
-        WriteStorage<'a, Velocity>,
+        WriteStorage<'a, Acceleration>,
     );
 
-    fn run(&mut self, (control_state, is_player, mut velocity): Self::SystemData) {
+    fn run(&mut self, (control_state, is_player, mut acceleration): Self::SystemData) {
         use specs::Join;
-        for (vel, _) in (&mut velocity, &is_player).join() {
+        for (acc, _) in (&mut acceleration, &is_player).join() {
+            acc.x = 0.0;
+            acc.y = 0.0;
             if control_state.left {
-                vel.x = -400.0;
+                acc.x -= self.thrust;
             }
             if control_state.right {
-                vel.x = 400.0;
-            }
-            if !control_state.left && !control_state.right {
-                vel.x = 0.0;
+                acc.x += self.thrust;
             }
             if control_state.up {
-                vel.y = -400.0;
+                acc.y -= self.thrust;
             }
             if control_state.down {
-                vel.y = 400.0;
-            }
-            if !control_state.up && !control_state.down {
-                vel.y = 0.0;
+                acc.y += self.thrust;
             }
         }
     }
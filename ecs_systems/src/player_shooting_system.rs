@@ -1,7 +1,10 @@
-use ecs_components::{HarmsAliens, IsPlayer, Position, ReapWhenOutside, Sprite, Velocity};
+use ecs_components::{
+    HarmsAliens, IsFast, IsPlayer, Layer, Position, ReapWhenOutside, Sprite, Velocity,
+};
 use geometry::Rect;
 use sdl_input::ControlState;
-use specs::{Entities, Read, ReadStorage, System, WriteStorage};
+use shared_types::{AudioQueue, RunStats, SoundEffect};
+use specs::{Entities, Read, ReadStorage, System, Write, WriteStorage};
 
 pub struct PlayerShooting {
     shot_size: (u32, u32),
@@ -21,11 +24,15 @@ impl<'a> System<'a> for PlayerShooting {
     type SystemData = (
         Read<'a, ControlState>,
         Entities<'a>,
+        Write<'a, AudioQueue>,
+        Write<'a, RunStats>,
         WriteStorage<'a, Position>,
         WriteStorage<'a, Velocity>,
         WriteStorage<'a, Sprite>,
+        WriteStorage<'a, Layer>,
         WriteStorage<'a, ReapWhenOutside>,
         WriteStorage<'a, HarmsAliens>,
+        WriteStorage<'a, IsFast>,
         ReadStorage<'a, IsPlayer>,
     );
 
@@ -34,11 +41,15 @@ impl<'a> System<'a> for PlayerShooting {
         (
             control_state,
             entities,
+            mut sounds,
+            mut stats,
             mut position,
             mut velocity,
             mut sprite,
+            mut layer,
             mut reap_when_outside,
             mut harms_aliens,
+            mut is_fast,
             is_player,
         ): Self::SystemData,
     ) {
@@ -55,6 +66,8 @@ impl<'a> System<'a> for PlayerShooting {
         }
 
         for pos in fire_positions {
+            sounds.play_at(SoundEffect::PlayerShot, pos.rect.center());
+            stats.shots_fired += 1;
             entities
                 .build_entity()
                 .with(
@@ -65,9 +78,51 @@ impl<'a> System<'a> for PlayerShooting {
                 )
                 .with(Velocity { x: 1500.0, y: 0.0 }, &mut velocity)
                 .with(Sprite::BasicShot, &mut sprite)
+                .with(Layer::Projectiles, &mut layer)
                 .with(ReapWhenOutside, &mut reap_when_outside)
                 .with(HarmsAliens, &mut harms_aliens)
+                .with(IsFast, &mut is_fast)
                 .build();
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geometry::RectSize;
+    use shared_types::{Camera, RecordingAudio};
+    use specs::{Builder, DispatcherBuilder, World, WorldExt};
+
+    #[test]
+    fn test_shooting_queues_a_panned_sound() {
+        let mut world = World::new();
+        ecs_components::register_components(&mut world);
+        let mut control_state = ControlState::new();
+        control_state.fire = true;
+        world.insert(control_state);
+        world.insert(AudioQueue::default());
+        world.insert(RunStats::default());
+        world
+            .create_entity()
+            .with(Position {
+                rect: Rect::new((100.0, 200.0).into(), (40, 20).into()),
+            })
+            .with(IsPlayer)
+            .build();
+        let mut dispatcher = DispatcherBuilder::new()
+            .with(PlayerShooting::new((10, 4)), "player_shooting", &[])
+            .build();
+
+        dispatcher.dispatch(&world);
+        dispatcher.dispatch(&world);
+
+        let mut audio = RecordingAudio::default();
+        world
+            .write_resource::<AudioQueue>()
+            .drain_into(&mut audio, &Camera::new(RectSize(480.0, 360.0)))
+            .unwrap();
+        assert_eq!(audio.played, vec![(SoundEffect::PlayerShot, -0.5)]);
+        assert_eq!(world.read_resource::<RunStats>().shots_fired, 1);
+    }
+}
@@ -1,58 +1,100 @@
-use ecs_components::{Draw, Position, ReapWhenOutside, Velocity};
+use ecs_components::{
+    Draw, Layer, Position, ReapWhenOutside, StarLayer, Starfield, StarfieldLayer, Twinkle,
+    Velocity, ZOrder,
+};
 use geometry::{Rect, RectSize};
 use rand::Rng;
-use shared_types::Arena;
+use shared_types::{Arena, ElapsedSeconds};
 use specs::{Entities, Read, System, WriteStorage};
 
+pub type StarData<'a> = (
+    Entities<'a>,
+    Read<'a, Arena>,
+    Read<'a, Starfield>,
+    WriteStorage<'a, Position>,
+    WriteStorage<'a, Velocity>,
+    WriteStorage<'a, Draw>,
+    WriteStorage<'a, Twinkle>,
+    WriteStorage<'a, Layer>,
+    WriteStorage<'a, ZOrder>,
+    WriteStorage<'a, ReapWhenOutside>,
+);
+
+// The number of stars expected in an area of the given number of square
+// pixels, rounded up or down at random so the average comes out right
+pub(crate) fn star_count(layer: &StarLayer, area: f32, rng: &mut impl Rng) -> usize {
+    let expected = layer.density * area / 10000.0;
+    (expected + rng.gen_range(0.0..1.0)).floor() as usize
+}
+
+pub(crate) fn spawn_star(
+    data: &mut StarData,
+    layer: &StarLayer,
+    layer_index: usize,
+    pos: geometry::Position,
+    rng: &mut impl Rng,
+) {
+    let (entities, _, _, position, velocity, draw, twinkle, draw_layer, z_order, reap) = data;
+    let radius = rng.gen_range(layer.min_radius..=layer.max_radius);
+    let mut builder = entities
+        .build_entity()
+        .with(
+            Position {
+                rect: Rect::new(pos, RectSize(0.0, 0.0)),
+            },
+            position,
+        )
+        .with(
+            Velocity {
+                x: -layer.speed,
+                y: 0.0,
+            },
+            velocity,
+        )
+        .with(
+            Draw::Star {
+                radius,
+                color: layer.color,
+            },
+            draw,
+        )
+        .with(Layer::Background, draw_layer)
+        .with(ZOrder(layer_index as i32), z_order)
+        .with(ReapWhenOutside, reap);
+    if layer.twinkle > 0.0 {
+        builder = builder.with(
+            Twinkle {
+                amplitude: layer.twinkle,
+                speed: layer.twinkle_speed * rng.gen_range(0.5..1.5),
+                phase: rng.gen_range(0.0..std::f32::consts::TAU),
+            },
+            twinkle,
+        );
+    }
+    builder.build();
+}
+
+// Spawns the stars entering the screen from the right
 pub struct StarSpawner;
 
 impl<'a> System<'a> for StarSpawner {
-    type SystemData = (
-        Entities<'a>,
-        Read<'a, Arena>,
-        WriteStorage<'a, Position>,
-        WriteStorage<'a, Velocity>,
-        WriteStorage<'a, Draw>,
-        WriteStorage<'a, ReapWhenOutside>,
-    );
+    type SystemData = (Read<'a, ElapsedSeconds>, StarData<'a>);
 
-    fn run(
-        &mut self,
-        (
-            entities,
-            arena,
-            mut position,
-            mut velocity,
-            mut draw,
-            mut reap_when_outside,
-        ): Self::SystemData,
-    ) {
-        let arena_rect = arena.0;
+    fn run(&mut self, (delta_time, mut data): Self::SystemData) {
+        let arena_rect = data.1 .0;
+        let starfield = data.2.clone();
         let mut rng = rand::thread_rng();
-        if rng.gen_range(0..10000) > 9500 {
-            let x = arena_rect.right() - 1.0;
-            let y = rng.gen_range(arena_rect.top()..arena_rect.bottom());
-            let pos = (x, y).into();
-            let radius = rng.gen_range(1..5);
-            let speed = rng.gen_range(15..50);
-            entities
-                .build_entity()
-                .with(
-                    Position {
-                        rect: Rect::new(pos, RectSize(0.0, 0.0)),
-                    },
-                    &mut position,
-                )
-                .with(
-                    Velocity {
-                        x: -speed as f32,
-                        y: 0.0,
-                    },
-                    &mut velocity,
-                )
-                .with(Draw::Star(radius), &mut draw)
-                .with(ReapWhenOutside, &mut reap_when_outside)
-                .build();
+        for (index, layer) in starfield.layers.iter().enumerate() {
+            if let StarfieldLayer::Stars(layer) = layer {
+                // The area scrolling in during this frame
+                let distance = layer.speed * delta_time.0;
+                let area = distance * arena_rect.height();
+                for _ in 0..star_count(layer, area, &mut rng) {
+                    let x = arena_rect.right() - 1.0 - rng.gen_range(0.0..=distance);
+                    let y = rng.gen_range(arena_rect.top()..arena_rect.bottom());
+                    spawn_star(&mut data, layer, index, (x, y).into(), &mut rng);
+                }
+            }
         }
     }
 }
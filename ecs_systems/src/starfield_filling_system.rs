@@ -0,0 +1,61 @@
+use ecs_components::{Layer, Position, Sprite, StarfieldLayer, Tiled, Velocity, ZOrder};
+use geometry::{Rect, RectSize};
+use rand::Rng;
+use specs::{System, WriteStorage};
+
+use crate::star_spawner_system::{spawn_star, star_count, StarData};
+
+// Creates the starfield covering the whole arena, for starting a new game.
+// Stars are scattered at random with the density of their layer, and an
+// entity is created for each image layer.
+pub struct StarfieldFilling;
+
+impl<'a> System<'a> for StarfieldFilling {
+    type SystemData = (
+        StarData<'a>,
+        WriteStorage<'a, Sprite>,
+        WriteStorage<'a, Tiled>,
+    );
+
+    fn run(&mut self, (mut data, mut sprite, mut tiled): Self::SystemData) {
+        let arena_rect = data.1 .0;
+        let starfield = data.2.clone();
+        let mut rng = rand::thread_rng();
+        let area = arena_rect.width() * arena_rect.height();
+        for (index, layer) in starfield.layers.iter().enumerate() {
+            match layer {
+                StarfieldLayer::Stars(layer) => {
+                    for _ in 0..star_count(layer, area, &mut rng) {
+                        let x = rng.gen_range(arena_rect.left()..arena_rect.right());
+                        let y = rng.gen_range(arena_rect.top()..arena_rect.bottom());
+                        spawn_star(&mut data, layer, index, (x, y).into(), &mut rng);
+                    }
+                }
+                StarfieldLayer::Image(layer) => {
+                    let (entities, _, _, position, velocity, _, _, draw_layer, z_order, _) =
+                        &mut data;
+                    entities
+                        .build_entity()
+                        .with(
+                            Position {
+                                rect: Rect::new(arena_rect.topleft(), RectSize(0.0, 0.0)),
+                            },
+                            position,
+                        )
+                        .with(
+                            Velocity {
+                                x: -layer.speed,
+                                y: 0.0,
+                            },
+                            velocity,
+                        )
+                        .with(layer.sprite, &mut sprite)
+                        .with(Tiled, &mut tiled)
+                        .with(Layer::Background, draw_layer)
+                        .with(ZOrder(index as i32), z_order)
+                        .build();
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use shared_types::{ElapsedSeconds, RunStats};
+use specs::{Read, System, Write};
+
+// Keeps the statistics that change every frame, rather than on events
+pub struct StatsTracking;
+
+impl<'a> System<'a> for StatsTracking {
+    type SystemData = (Read<'a, ElapsedSeconds>, Write<'a, RunStats>);
+
+    fn run(&mut self, (delta_time, mut stats): Self::SystemData) {
+        stats.seconds_survived += delta_time.0;
+    }
+}
@@ -0,0 +1,34 @@
+use shared_types::SystemTimes;
+use specs::{System, World};
+use std::time::Instant;
+
+// Runs another system and records how long it took
+pub struct Timed<S> {
+    system: S,
+    name: &'static str,
+    times: SystemTimes,
+}
+
+impl<S> Timed<S> {
+    pub fn new(system: S, name: &'static str, times: SystemTimes) -> Self {
+        Timed {
+            system,
+            name,
+            times,
+        }
+    }
+}
+
+impl<'a, S: System<'a>> System<'a> for Timed<S> {
+    type SystemData = S::SystemData;
+
+    fn run(&mut self, data: Self::SystemData) {
+        let start = Instant::now();
+        self.system.run(data);
+        self.times.record(self.name, start.elapsed().as_secs_f32());
+    }
+
+    fn setup(&mut self, world: &mut World) {
+        self.system.setup(world);
+    }
+}
@@ -0,0 +1,16 @@
+use ecs_components::Twinkle;
+use shared_types::ElapsedSeconds;
+use specs::{Read, System, WriteStorage};
+
+pub struct Twinkling;
+
+impl<'a> System<'a> for Twinkling {
+    type SystemData = (Read<'a, ElapsedSeconds>, WriteStorage<'a, Twinkle>);
+
+    fn run(&mut self, (delta_time, mut twinkle): Self::SystemData) {
+        use specs::Join;
+        for twinkle in (&mut twinkle).join() {
+            twinkle.phase = (twinkle.phase + twinkle.speed * delta_time.0) % std::f32::consts::TAU;
+        }
+    }
+}
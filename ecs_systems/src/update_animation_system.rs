@@ -0,0 +1,29 @@
+use ecs_components::{Animation, OnFinish};
+use shared_types::ElapsedSeconds;
+use specs::{Entities, LazyUpdate, Read, System, WriteStorage};
+
+pub struct UpdateAnimation;
+
+impl<'a> System<'a> for UpdateAnimation {
+    type SystemData = (
+        Entities<'a>,
+        Read<'a, LazyUpdate>,
+        Read<'a, ElapsedSeconds>,
+        WriteStorage<'a, Animation>,
+    );
+
+    fn run(&mut self, (entities, updater, delta_time, mut animation): Self::SystemData) {
+        use specs::Join;
+        for (entity, animation) in (&entities, &mut animation).join() {
+            if animation.advance(delta_time.0) {
+                match animation.on_finish {
+                    OnFinish::Hold => {}
+                    OnFinish::RemoveAnimation => updater.remove::<Animation>(entity),
+                    OnFinish::DeleteEntity => {
+                        let _res = entities.delete(entity);
+                    }
+                }
+            }
+        }
+    }
+}
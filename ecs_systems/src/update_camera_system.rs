@@ -0,0 +1,35 @@
+use ecs_components::{IsPlayer, Position};
+use geometry::Vec2;
+use rand::Rng;
+use shared_types::{Accessibility, Camera, ElapsedSeconds};
+use specs::{Read, ReadStorage, System, Write};
+
+pub struct UpdateCamera;
+
+impl<'a> System<'a> for UpdateCamera {
+    type SystemData = (
+        Read<'a, ElapsedSeconds>,
+        Read<'a, Accessibility>,
+        Write<'a, Camera>,
+        ReadStorage<'a, Position>,
+        ReadStorage<'a, IsPlayer>,
+    );
+
+    fn run(
+        &mut self,
+        (delta_time, accessibility, mut camera, position, is_player): Self::SystemData,
+    ) {
+        use specs::Join;
+        let mut rng = rand::thread_rng();
+        let target = (&position, &is_player)
+            .join()
+            .next()
+            .map(|(pos, _)| pos.rect.center());
+        let shake = if accessibility.screen_shake {
+            Vec2::new(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0))
+        } else {
+            Vec2::ZERO
+        };
+        camera.update(delta_time.0, target, shake);
+    }
+}
@@ -0,0 +1,31 @@
+use ecs_components::{Particle, ParticleEffects};
+use shared_types::ElapsedSeconds;
+use specs::{Entities, Read, System, WriteStorage};
+
+// Ages particles, updating their color and size, and removes them when
+// they reach the end of their life
+pub struct UpdateParticles;
+
+impl<'a> System<'a> for UpdateParticles {
+    type SystemData = (
+        Entities<'a>,
+        Read<'a, ElapsedSeconds>,
+        Read<'a, ParticleEffects>,
+        WriteStorage<'a, Particle>,
+    );
+
+    fn run(&mut self, (entities, delta_time, effects, mut particle): Self::SystemData) {
+        use specs::Join;
+        for (entity, particle) in (&entities, &mut particle).join() {
+            particle.age += delta_time.0;
+            if particle.age >= particle.lifetime {
+                let _res = entities.delete(entity);
+                continue;
+            }
+            if let Some(definition) = effects.get(&particle.effect) {
+                particle.color = definition.color_at(particle.progress());
+                particle.scale = definition.scale_at(particle.progress());
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use ecs_components::{Position, Velocity};
+use ecs_components::{Acceleration, Drag, MaxSpeed, Position, Velocity};
 use shared_types::ElapsedSeconds;
 use specs::{Read, ReadStorage, System, WriteStorage};
 
@@ -7,14 +7,110 @@ pub struct UpdatePos;
 impl<'a> System<'a> for UpdatePos {
     type SystemData = (
         Read<'a, ElapsedSeconds>,
-        ReadStorage<'a, Velocity>,
+        ReadStorage<'a, Acceleration>,
+        ReadStorage<'a, Drag>,
+        ReadStorage<'a, MaxSpeed>,
+        WriteStorage<'a, Velocity>,
         WriteStorage<'a, Position>,
     );
 
-    fn run(&mut self, (delta_time, velocity, mut position): Self::SystemData) {
+    fn run(
+        &mut self,
+        (delta_time, acceleration, drag, max_speed, mut velocity, mut position): Self::SystemData,
+    ) {
         use specs::Join;
-        for (v, p) in (&velocity, &mut position).join() {
-            p.rect.r#move(v.x * delta_time.0, v.y * delta_time.0);
+        let dt = delta_time.0;
+        // Semi-implicit Euler: the velocity is updated first, and the new
+        // velocity is used for moving the entity.
+        for (v, p, a, d, m) in (
+            &mut velocity,
+            &mut position,
+            acceleration.maybe(),
+            drag.maybe(),
+            max_speed.maybe(),
+        )
+            .join()
+        {
+            let mut new_velocity = v.vec2();
+            if let Some(a) = a {
+                new_velocity += a.vec2() * dt;
+            }
+            if let Some(d) = d {
+                new_velocity *= (-d.rate * dt).exp();
+            }
+            if let Some(m) = m {
+                if new_velocity.length() > m.speed {
+                    new_velocity = new_velocity.with_length(m.speed);
+                }
+            }
+            v.set(new_velocity);
+            p.rect.translate(new_velocity * dt);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geometry::Rect;
+    use specs::{Builder, Entity, EntityBuilder, RunNow, World, WorldExt};
+
+    fn add_moving(world: &mut World, x: f32, y: f32) -> EntityBuilder<'_> {
+        world
+            .create_entity()
+            .with(Position {
+                rect: Rect::new((0.0, 0.0).into(), (10, 10).into()),
+            })
+            .with(Velocity { x, y })
+    }
+
+    fn moved(world: &World, entity: Entity) -> (f32, f32, f32, f32) {
+        let velocity = world.read_storage::<Velocity>();
+        let velocity = velocity.get(entity).unwrap();
+        let position = world.read_storage::<Position>();
+        let position = position.get(entity).unwrap();
+        (
+            velocity.x,
+            velocity.y,
+            position.rect.left(),
+            position.rect.top(),
+        )
+    }
+
+    fn assert_close(actual: (f32, f32, f32, f32), expected: (f32, f32, f32, f32)) {
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-4;
+        assert!(
+            close(actual.0, expected.0)
+                && close(actual.1, expected.1)
+                && close(actual.2, expected.2)
+                && close(actual.3, expected.3),
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_acceleration_drag_and_max_speed() {
+        let mut world = World::new();
+        ecs_components::register_components(&mut world);
+        world.insert(ElapsedSeconds(0.5));
+        let accelerating = add_moving(&mut world, 10.0, 0.0)
+            .with(Acceleration { x: 4.0, y: 0.0 })
+            .build();
+        let dragged = add_moving(&mut world, 100.0, 0.0)
+            .with(Drag { rate: 2.0 })
+            .build();
+        let capped = add_moving(&mut world, 300.0, 400.0)
+            .with(MaxSpeed { speed: 100.0 })
+            .build();
+
+        UpdatePos.run_now(&world);
+
+        // Moved with the new velocity of 12, not the old one of 10
+        assert_close(moved(&world, accelerating), (12.0, 0.0, 6.0, 0.0));
+        let decayed = 100.0 * (-2.0f32 * 0.5).exp();
+        assert_close(moved(&world, dragged), (decayed, 0.0, decayed * 0.5, 0.0));
+        assert_close(moved(&world, capped), (60.0, 80.0, 30.0, 40.0));
+    }
+}
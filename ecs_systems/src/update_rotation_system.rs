@@ -0,0 +1,102 @@
+use ecs_components::{Rotation, RotationKind, Velocity};
+use shared_types::ElapsedSeconds;
+use specs::{Read, ReadStorage, System, WriteStorage};
+
+pub struct UpdateRotation;
+
+impl<'a> System<'a> for UpdateRotation {
+    type SystemData = (
+        Read<'a, ElapsedSeconds>,
+        ReadStorage<'a, RotationKind>,
+        ReadStorage<'a, Velocity>,
+        WriteStorage<'a, Rotation>,
+    );
+
+    fn run(&mut self, (delta_time, kind, velocity, mut rotation): Self::SystemData) {
+        use specs::Join;
+        for (kind, vel, rotation) in (&kind, velocity.maybe(), &mut rotation).join() {
+            match kind {
+                RotationKind::AlignWithVelocity => {
+                    if let Some(vel) = vel {
+                        let vel = vel.vec2();
+                        if vel.length_squared() > 0.0 {
+                            rotation.degrees = vel.angle().to_degrees();
+                        }
+                    }
+                }
+                RotationKind::Spin { degrees_per_second } => {
+                    rotation.degrees =
+                        (rotation.degrees + degrees_per_second * delta_time.0) % 360.0;
+                }
+                RotationKind::Bank {
+                    max_degrees,
+                    full_speed,
+                } => {
+                    let vertical_speed = vel.map_or(0.0, |v| v.y);
+                    // Without a full speed to compare with there is no tilt
+                    let amount = if *full_speed > 0.0 {
+                        (vertical_speed / full_speed).clamp(-1.0, 1.0)
+                    } else {
+                        0.0
+                    };
+                    rotation.degrees = amount * max_degrees;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use specs::{Builder, RunNow, World, WorldExt};
+
+    // The rotation of an entity starting at start_degrees after one run over
+    // half a second
+    fn rotated(kind: RotationKind, velocity: Option<(f32, f32)>, start_degrees: f32) -> f32 {
+        let mut world = World::new();
+        ecs_components::register_components(&mut world);
+        world.insert(ElapsedSeconds(0.5));
+        let mut builder = world.create_entity().with(kind).with(Rotation {
+            degrees: start_degrees,
+            pivot: None,
+        });
+        if let Some((x, y)) = velocity {
+            builder = builder.with(Velocity { x, y });
+        }
+        let entity = builder.build();
+
+        UpdateRotation.run_now(&world);
+        let rotation = world.read_storage::<Rotation>();
+        rotation.get(entity).unwrap().degrees
+    }
+
+    #[test]
+    fn test_align_with_velocity() {
+        let align = || RotationKind::AlignWithVelocity;
+        assert_eq!(rotated(align(), Some((0.0, 10.0)), 0.0), 90.0);
+        assert_eq!(rotated(align(), Some((0.0, 0.0)), 45.0), 45.0);
+        assert_eq!(rotated(align(), None, 45.0), 45.0);
+    }
+
+    #[test]
+    fn test_spin_wraps() {
+        let spin = RotationKind::Spin {
+            degrees_per_second: 90.0,
+        };
+        assert_eq!(rotated(spin, None, 350.0), 35.0);
+    }
+
+    #[test]
+    fn test_bank_is_clamped() {
+        let bank = |full_speed| RotationKind::Bank {
+            max_degrees: 20.0,
+            full_speed,
+        };
+        assert_eq!(rotated(bank(100.0), Some((0.0, 50.0)), 0.0), 10.0);
+        assert_eq!(rotated(bank(100.0), Some((0.0, 1000.0)), 0.0), 20.0);
+        assert_eq!(rotated(bank(100.0), Some((0.0, -1000.0)), 0.0), -20.0);
+        assert_eq!(rotated(bank(0.0), Some((0.0, 0.0)), 0.0), 0.0);
+        assert_eq!(rotated(bank(0.0), Some((0.0, 50.0)), 0.0), 0.0);
+    }
+}
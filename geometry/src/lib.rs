@@ -1,7 +1,9 @@
 mod position;
 mod rect;
 mod rectsize;
+mod vec2;
 
 pub use position::Position;
 pub use rect::Rect;
 pub use rectsize::RectSize;
+pub use vec2::Vec2;
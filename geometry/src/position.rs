@@ -1,3 +1,7 @@
+use std::ops::{Add, Sub};
+
+use crate::Vec2;
+
 #[derive(Debug, Copy, Clone, Default, PartialEq)]
 pub struct Position(pub f32, pub f32);
 
@@ -12,3 +16,26 @@ impl From<(f32, f32)> for Position {
         Position(x, y)
     }
 }
+
+impl From<Vec2> for Position {
+    fn from(vec: Vec2) -> Self {
+        Position(vec.x, vec.y)
+    }
+}
+
+impl Add<Vec2> for Position {
+    type Output = Position;
+
+    fn add(self, offset: Vec2) -> Position {
+        Position(self.0 + offset.x, self.1 + offset.y)
+    }
+}
+
+// The difference between two positions is the vector from other to self
+impl Sub for Position {
+    type Output = Vec2;
+
+    fn sub(self, other: Position) -> Vec2 {
+        Vec2::new(self.0 - other.0, self.1 - other.1)
+    }
+}
@@ -1,8 +1,6 @@
-#![allow(dead_code)] // TODO: Remove
+use crate::{Position, RectSize, Vec2};
 
-use crate::{Position, RectSize};
-
-#[derive(Debug, Copy, Clone, Default)]
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
 pub struct Rect {
     topleft: Position,
     size: RectSize,
@@ -85,22 +83,323 @@ impl Rect {
             .into()
     }
 
+    pub fn topleft(&self) -> Position {
+        self.topleft
+    }
+
+    pub fn size(&self) -> RectSize {
+        self.size
+    }
+
     pub fn r#move(&mut self, dx: f32, dy: f32) {
         self.topleft.0 += dx;
         self.topleft.1 += dy;
     }
 
+    pub fn translate(&mut self, offset: Vec2) {
+        self.topleft = self.topleft + offset;
+    }
+
+    pub fn translated(&self, offset: Vec2) -> Rect {
+        Rect::new(self.topleft + offset, self.size)
+    }
+
     pub fn overlaps(&self, other: &Rect) -> bool {
         !(self.left() > other.right()
             || other.left() > self.right()
             || self.top() > other.bottom()
             || other.top() > self.bottom())
     }
+
+    // The overlapping part of the two rects. Like overlaps, rects that only
+    // share an edge count as overlapping, giving an intersection with zero
+    // width or height.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        if !self.overlaps(other) {
+            return None;
+        }
+        let left = self.left().max(other.left());
+        let top = self.top().max(other.top());
+        let right = self.right().min(other.right());
+        let bottom = self.bottom().min(other.bottom());
+        Some(Rect::new(
+            (left, top).into(),
+            RectSize(right - left, bottom - top),
+        ))
+    }
+
+    // The smallest rect containing both rects
+    pub fn union(&self, other: &Rect) -> Rect {
+        let left = self.left().min(other.left());
+        let top = self.top().min(other.top());
+        let right = self.right().max(other.right());
+        let bottom = self.bottom().max(other.bottom());
+        Rect::new((left, top).into(), RectSize(right - left, bottom - top))
+    }
+
+    // Points on the edges are inside the rect
+    pub fn contains_point(&self, point: Position) -> bool {
+        point.0 >= self.left()
+            && point.0 <= self.right()
+            && point.1 >= self.top()
+            && point.1 <= self.bottom()
+    }
+
+    pub fn contains_rect(&self, other: &Rect) -> bool {
+        other.left() >= self.left()
+            && other.right() <= self.right()
+            && other.top() >= self.top()
+            && other.bottom() <= self.bottom()
+    }
+
+    // Grows the rect by dx on the left and on the right side, and by dy on
+    // the top and on the bottom, keeping the center in place. Negative
+    // amounts shrink the rect, but never below a size of zero.
+    pub fn inflate(&self, dx: f32, dy: f32) -> Rect {
+        let center = self.center();
+        let width = (self.width() + 2.0 * dx).max(0.0);
+        let height = (self.height() + 2.0 * dy).max(0.0);
+        Rect::new(
+            (center.0 - width / 2.0, center.1 - height / 2.0).into(),
+            RectSize(width, height),
+        )
+    }
+
+    pub fn deflate(&self, dx: f32, dy: f32) -> Rect {
+        self.inflate(-dx, -dy)
+    }
+
+    // Moves the rect the shortest distance needed to keep it inside bounds.
+    // A rect larger than bounds is aligned with the right and bottom edges.
+    pub fn clamp_inside(&mut self, bounds: &Rect) {
+        if self.left() < bounds.left() {
+            self.set_left(bounds.left());
+        }
+        if self.top() < bounds.top() {
+            self.set_top(bounds.top());
+        }
+        if self.right() > bounds.right() {
+            self.set_right(bounds.right());
+        }
+        if self.bottom() > bounds.bottom() {
+            self.set_bottom(bounds.bottom());
+        }
+    }
+
+    // The distance from point to the closest point of the rect, which is
+    // zero for points inside it
+    pub fn distance_to_point(&self, point: Position) -> f32 {
+        let dx = (self.left() - point.0).max(point.0 - self.right()).max(0.0);
+        let dy = (self.top() - point.1).max(point.1 - self.bottom()).max(0.0);
+        Vec2::new(dx, dy).length()
+    }
+
+    // Splits the rect into four equally sized parts, ordered top left,
+    // top right, bottom left and bottom right
+    pub fn quadrants(&self) -> [Rect; 4] {
+        let size = RectSize(self.width() / 2.0, self.height() / 2.0);
+        let center = self.center();
+        [
+            Rect::new(self.topleft, size),
+            Rect::new((center.0, self.top()).into(), size),
+            Rect::new((self.left(), center.1).into(), size),
+            Rect::new(center, size),
+        ]
+    }
+
+    // Checks whether this rect touches other at any point while moving
+    // from its current position by motion. This is a ray vs. rect test
+    // against other grown by the size of this rect (the Minkowski sum).
+    pub fn sweep_overlaps(&self, motion: Vec2, other: &Rect) -> bool {
+        let expanded = Rect::new(
+            (other.left() - self.width(), other.top() - self.height()).into(),
+            RectSize(other.width() + self.width(), other.height() + self.height()),
+        );
+        let mut t_enter = 0.0f32;
+        let mut t_exit = 1.0f32;
+        let slabs = [
+            (self.left(), motion.x, expanded.left(), expanded.right()),
+            (self.top(), motion.y, expanded.top(), expanded.bottom()),
+        ];
+        for (start, delta, low, high) in slabs.iter() {
+            if *delta == 0.0 {
+                if start < low || start > high {
+                    return false;
+                }
+            } else {
+                let t0 = (low - start) / delta;
+                let t1 = (high - start) / delta;
+                t_enter = t_enter.max(t0.min(t1));
+                t_exit = t_exit.min(t0.max(t1));
+                if t_enter > t_exit {
+                    return false;
+                }
+            }
+        }
+        true
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+
+    // Whole and half coordinates keep the arithmetic in the properties exact
+    fn coordinate() -> impl Strategy<Value = f32> {
+        (-2000i32..2000).prop_map(|c| c as f32 / 2.0)
+    }
+
+    fn length() -> impl Strategy<Value = f32> {
+        (0i32..1000).prop_map(|l| l as f32 / 2.0)
+    }
+
+    prop_compose! {
+        fn rect()(x in coordinate(), y in coordinate(), w in length(), h in length()) -> Rect {
+            Rect::new(Position(x, y), RectSize(w, h))
+        }
+    }
+
+    prop_compose! {
+        fn point()(x in coordinate(), y in coordinate()) -> Position {
+            Position(x, y)
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_overlaps_is_symmetric(a in rect(), b in rect()) {
+            prop_assert_eq!(a.overlaps(&b), b.overlaps(&a));
+        }
+
+        #[test]
+        fn prop_touching_edges_overlap(a in rect(), h in length(), offset in coordinate()) {
+            let right = Rect::new((a.right(), a.top() + offset).into(), RectSize(1.0, h));
+            let below = Rect::new((a.left() + offset, a.bottom()).into(), RectSize(h, 1.0));
+            prop_assert_eq!(a.overlaps(&right), offset <= a.height() && offset + h >= 0.0);
+            prop_assert_eq!(a.overlaps(&below), offset <= a.width() && offset + h >= 0.0);
+        }
+
+        #[test]
+        fn prop_intersection_matches_overlaps(a in rect(), b in rect()) {
+            prop_assert_eq!(a.intersection(&b).is_some(), a.overlaps(&b));
+            prop_assert_eq!(a.intersection(&b), b.intersection(&a));
+        }
+
+        #[test]
+        fn prop_intersection_is_inside_both(a in rect(), b in rect()) {
+            if let Some(intersection) = a.intersection(&b) {
+                prop_assert!(a.contains_rect(&intersection));
+                prop_assert!(b.contains_rect(&intersection));
+            }
+        }
+
+        #[test]
+        fn prop_union_contains_both(a in rect(), b in rect()) {
+            let union = a.union(&b);
+            prop_assert!(union.contains_rect(&a));
+            prop_assert!(union.contains_rect(&b));
+            prop_assert_eq!(union, b.union(&a));
+        }
+
+        #[test]
+        fn prop_rect_contains_itself_and_its_corners(a in rect()) {
+            prop_assert!(a.contains_rect(&a));
+            prop_assert!(a.contains_point(a.topleft()));
+            prop_assert!(a.contains_point((a.right(), a.bottom()).into()));
+            prop_assert!(a.contains_point(a.center()));
+        }
+
+        #[test]
+        fn prop_contains_point_matches_overlapping_point_rect(a in rect(), p in point()) {
+            let point_rect = Rect::new(p, RectSize(0.0, 0.0));
+            prop_assert_eq!(a.contains_point(p), a.overlaps(&point_rect));
+            prop_assert_eq!(a.contains_point(p), a.contains_rect(&point_rect));
+        }
+
+        #[test]
+        fn prop_inflate_keeps_center(a in rect(), dx in length(), dy in length()) {
+            let inflated = a.inflate(dx, dy);
+            prop_assert_eq!(inflated.center(), a.center());
+            prop_assert!(inflated.contains_rect(&a));
+            prop_assert_eq!(inflated.deflate(dx, dy), a);
+        }
+
+        #[test]
+        fn prop_deflate_never_goes_negative(a in rect(), dx in length(), dy in length()) {
+            let deflated = a.deflate(dx, dy);
+            prop_assert!(deflated.width() >= 0.0);
+            prop_assert!(deflated.height() >= 0.0);
+            prop_assert!(a.contains_rect(&deflated));
+        }
+
+        #[test]
+        fn prop_clamp_inside_keeps_size(a in rect(), bounds in rect()) {
+            let mut clamped = a;
+            clamped.clamp_inside(&bounds);
+            prop_assert_eq!(clamped.size(), a.size());
+            if a.width() <= bounds.width() && a.height() <= bounds.height() {
+                prop_assert!(bounds.contains_rect(&clamped));
+            }
+            if bounds.contains_rect(&a) {
+                prop_assert_eq!(clamped, a);
+            }
+        }
+
+        #[test]
+        fn prop_distance_is_zero_exactly_inside(a in rect(), p in point()) {
+            let distance = a.distance_to_point(p);
+            prop_assert!(distance >= 0.0);
+            prop_assert_eq!(distance == 0.0, a.contains_point(p));
+        }
+
+        #[test]
+        fn prop_distance_is_at_most_distance_to_corner(a in rect(), p in point()) {
+            prop_assert!(a.distance_to_point(p) <= (p - a.topleft()).length());
+        }
+
+        #[test]
+        fn prop_quadrants_cover_rect(a in rect()) {
+            let quadrants = a.quadrants();
+            let union = quadrants.iter().fold(quadrants[0], |acc, q| acc.union(q));
+            prop_assert_eq!(union, a);
+            for quadrant in quadrants.iter() {
+                prop_assert!(a.contains_rect(quadrant));
+                prop_assert_eq!(quadrant.width() * 2.0, a.width());
+                prop_assert_eq!(quadrant.height() * 2.0, a.height());
+            }
+            prop_assert_eq!(quadrants[3].topleft(), a.center());
+        }
+    }
+
+    #[test]
+    fn test_touching_edges_overlap() {
+        let a = Rect::new((0.0, 0.0).into(), (10, 10).into());
+        let b = Rect::new((10.0, 0.0).into(), (10, 10).into());
+        let c = Rect::new((10.5, 0.0).into(), (10, 10).into());
+        assert!(a.overlaps(&b));
+        assert!(!a.overlaps(&c));
+        assert_eq!(
+            a.intersection(&b),
+            Some(Rect::new((10.0, 0.0).into(), RectSize(0.0, 10.0)))
+        );
+    }
+
+    #[test]
+    fn test_clamp_inside() {
+        let bounds = Rect::new((0.0, 32.0).into(), (1200, 568).into());
+        let mut rect = Rect::new((-10.0, 590.0).into(), (20, 20).into());
+        rect.clamp_inside(&bounds);
+        assert_eq!(rect.topleft(), (0.0, 580.0).into());
+    }
+
+    #[test]
+    fn test_distance_to_point() {
+        let rect = Rect::new((0.0, 0.0).into(), (10, 10).into());
+        assert_eq!(rect.distance_to_point((13.0, 14.0).into()), 5.0);
+        assert_eq!(rect.distance_to_point((5.0, -2.0).into()), 2.0);
+        assert_eq!(rect.distance_to_point((5.0, 5.0).into()), 0.0);
+    }
 
     #[test]
     fn test_inputs_are_stored() {
@@ -126,4 +425,48 @@ mod tests {
         assert_eq!(rect.midtop(), (110.0, 200.0).into());
         assert_eq!(rect.midbottom(), (110.0, 250.0).into());
     }
+
+    #[test]
+    fn test_translate() {
+        let mut rect = Rect::new((100.0, 200.0).into(), (20, 50).into());
+        assert_eq!(
+            rect.translated(Vec2::new(5.0, -5.0)).topleft(),
+            (105.0, 195.0).into()
+        );
+        rect.translate(Vec2::new(-100.0, 10.0));
+        assert_eq!(rect.topleft(), (0.0, 210.0).into());
+        assert_eq!(rect.center() - rect.topleft(), Vec2::new(10.0, 25.0));
+    }
+
+    #[test]
+    fn test_sweep_passing_through() {
+        let shot = Rect::new((0.0, 10.0).into(), (10, 2).into());
+        let target = Rect::new((100.0, 0.0).into(), (20, 20).into());
+        assert!(!shot.overlaps(&target));
+        assert!(shot.sweep_overlaps(Vec2::new(300.0, 0.0), &target));
+    }
+
+    #[test]
+    fn test_sweep_stopping_short() {
+        let shot = Rect::new((0.0, 10.0).into(), (10, 2).into());
+        let target = Rect::new((100.0, 0.0).into(), (20, 20).into());
+        assert!(!shot.sweep_overlaps(Vec2::new(50.0, 0.0), &target));
+        assert!(!shot.sweep_overlaps(Vec2::new(-300.0, 0.0), &target));
+    }
+
+    #[test]
+    fn test_sweep_passing_beside() {
+        let shot = Rect::new((0.0, 30.0).into(), (10, 2).into());
+        let target = Rect::new((100.0, 0.0).into(), (20, 20).into());
+        assert!(!shot.sweep_overlaps(Vec2::new(300.0, 0.0), &target));
+        assert!(!shot.sweep_overlaps(Vec2::new(300.0, 1.0), &target));
+        assert!(shot.sweep_overlaps(Vec2::new(300.0, -30.0), &target));
+    }
+
+    #[test]
+    fn test_sweep_without_movement_is_overlap() {
+        let shot = Rect::new((105.0, 10.0).into(), (10, 2).into());
+        let target = Rect::new((100.0, 0.0).into(), (20, 20).into());
+        assert!(shot.sweep_overlaps(Vec2::new(0.0, 0.0), &target));
+    }
 }
@@ -1,4 +1,4 @@
-#[derive(Debug, Copy, Clone, Default)]
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
 pub struct RectSize(pub f32, pub f32);
 
 impl From<(u32, u32)> for RectSize {
@@ -0,0 +1,202 @@
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use crate::Position;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Vec2 {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Vec2 {
+    pub const ZERO: Vec2 = Vec2 { x: 0.0, y: 0.0 };
+
+    pub fn new(x: f32, y: f32) -> Self {
+        Vec2 { x, y }
+    }
+
+    // A unit vector pointing in the direction of angle, which is in radians
+    // measured from the positive x axis. As y grows downwards on the screen
+    // positive angles turn clockwise.
+    pub fn from_angle(angle: f32) -> Self {
+        Vec2::new(angle.cos(), angle.sin())
+    }
+
+    pub fn length(&self) -> f32 {
+        self.length_squared().sqrt()
+    }
+
+    pub fn length_squared(&self) -> f32 {
+        self.dot(*self)
+    }
+
+    // Returns a vector of length 1 in the same direction. The zero vector
+    // has no direction, so it is returned unchanged.
+    pub fn normalize(&self) -> Vec2 {
+        let length = self.length();
+        if length == 0.0 {
+            *self
+        } else {
+            *self / length
+        }
+    }
+
+    pub fn with_length(&self, length: f32) -> Vec2 {
+        self.normalize() * length
+    }
+
+    pub fn dot(&self, other: Vec2) -> f32 {
+        self.x * other.x + self.y * other.y
+    }
+
+    pub fn rotate(&self, angle: f32) -> Vec2 {
+        let (sin, cos) = angle.sin_cos();
+        Vec2::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+    }
+
+    pub fn lerp(&self, other: Vec2, t: f32) -> Vec2 {
+        *self + (other - *self) * t
+    }
+
+    // The angle of this vector in radians, in the same convention as from_angle
+    pub fn angle(&self) -> f32 {
+        self.y.atan2(self.x)
+    }
+
+    // The signed angle in radians to rotate this vector by to point along other
+    pub fn angle_to(&self, other: Vec2) -> f32 {
+        let cross = self.x * other.y - self.y * other.x;
+        cross.atan2(self.dot(other))
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, other: Vec2) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Vec2 {
+    fn sub_assign(&mut self, other: Vec2) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Vec2 {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<f32> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, factor: f32) -> Vec2 {
+        Vec2::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl Mul<Vec2> for f32 {
+    type Output = Vec2;
+
+    fn mul(self, vec: Vec2) -> Vec2 {
+        vec * self
+    }
+}
+
+impl MulAssign<f32> for Vec2 {
+    fn mul_assign(&mut self, factor: f32) {
+        *self = *self * factor;
+    }
+}
+
+impl Div<f32> for Vec2 {
+    type Output = Vec2;
+
+    fn div(self, divisor: f32) -> Vec2 {
+        Vec2::new(self.x / divisor, self.y / divisor)
+    }
+}
+
+impl From<(f32, f32)> for Vec2 {
+    fn from((x, y): (f32, f32)) -> Self {
+        Vec2::new(x, y)
+    }
+}
+
+impl From<Position> for Vec2 {
+    fn from(position: Position) -> Self {
+        Vec2::new(position.0, position.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    fn assert_close(a: Vec2, b: Vec2) {
+        assert!((a - b).length() < 1e-5, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_operators() {
+        let a = Vec2::new(1.0, 2.0);
+        let b = Vec2::new(3.0, -4.0);
+        assert_eq!(a + b, Vec2::new(4.0, -2.0));
+        assert_eq!(a - b, Vec2::new(-2.0, 6.0));
+        assert_eq!(-a, Vec2::new(-1.0, -2.0));
+        assert_eq!(a * 2.0, Vec2::new(2.0, 4.0));
+        assert_eq!(2.0 * a, Vec2::new(2.0, 4.0));
+        assert_eq!(b / 2.0, Vec2::new(1.5, -2.0));
+    }
+
+    #[test]
+    fn test_length_and_normalize() {
+        let v = Vec2::new(3.0, 4.0);
+        assert_eq!(v.length(), 5.0);
+        assert_eq!(v.length_squared(), 25.0);
+        assert_close(v.normalize(), Vec2::new(0.6, 0.8));
+        assert_close(v.with_length(10.0), Vec2::new(6.0, 8.0));
+        assert_eq!(Vec2::ZERO.normalize(), Vec2::ZERO);
+    }
+
+    #[test]
+    fn test_dot_and_lerp() {
+        assert_eq!(Vec2::new(1.0, 2.0).dot(Vec2::new(3.0, 4.0)), 11.0);
+        let a = Vec2::new(0.0, 10.0);
+        let b = Vec2::new(10.0, 20.0);
+        assert_eq!(a.lerp(b, 0.0), a);
+        assert_eq!(a.lerp(b, 1.0), b);
+        assert_eq!(a.lerp(b, 0.5), Vec2::new(5.0, 15.0));
+    }
+
+    #[test]
+    fn test_angles() {
+        assert_close(Vec2::from_angle(0.0), Vec2::new(1.0, 0.0));
+        assert_close(Vec2::from_angle(PI / 2.0), Vec2::new(0.0, 1.0));
+        assert_close(Vec2::new(1.0, 0.0).rotate(PI / 2.0), Vec2::new(0.0, 1.0));
+        assert!((Vec2::new(0.0, -2.0).angle() + PI / 2.0).abs() < 1e-5);
+        let a = Vec2::new(1.0, 0.0);
+        assert!((a.angle_to(Vec2::new(0.0, 1.0)) - PI / 2.0).abs() < 1e-5);
+        assert!((a.angle_to(Vec2::new(0.0, -1.0)) + PI / 2.0).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,145 @@
+use std::error::Error;
+use std::path::Path;
+
+use geometry::Position;
+use render::{Color, Screen};
+use serde::Deserialize;
+
+// The point on the screen a widget is placed relative to. The same point
+// of the widget is put there, so a widget anchored to the top right has
+// its top right corner in the top right corner of the screen.
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Anchor {
+    fn fractions(&self) -> (f32, f32) {
+        match self {
+            Anchor::TopLeft => (0.0, 0.0),
+            Anchor::Top => (0.5, 0.0),
+            Anchor::TopRight => (1.0, 0.0),
+            Anchor::Left => (0.0, 0.5),
+            Anchor::Center => (0.5, 0.5),
+            Anchor::Right => (1.0, 0.5),
+            Anchor::BottomLeft => (0.0, 1.0),
+            Anchor::Bottom => (0.5, 1.0),
+            Anchor::BottomRight => (1.0, 1.0),
+        }
+    }
+
+    // The top left corner of a widget of the given size
+    pub fn place(&self, screen: Screen, offset: (f32, f32), size: (f32, f32)) -> Position {
+        let (fx, fy) = self.fractions();
+        Position(
+            screen.width as f32 * fx + offset.0 - size.0 * fx,
+            screen.height as f32 * fy + offset.1 - size.1 * fy,
+        )
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WidgetKind {
+    Score,
+    // A ship icon for every life left
+    Lives,
+    Multiplier,
+    BossHealth,
+    WeaponLevel,
+    Wave,
+}
+
+fn white() -> Color {
+    Color::WHITE
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WidgetLayout {
+    pub kind: WidgetKind,
+    pub anchor: Anchor,
+    // Moves the widget away from the anchor, in pixels
+    #[serde(default)]
+    pub offset: (f32, f32),
+    // The size of bars, and the height of icons. Text widgets take the size
+    // of their text.
+    #[serde(default)]
+    pub size: (f32, f32),
+    #[serde(default = "white")]
+    pub color: Color,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct HudLayout {
+    pub widgets: Vec<WidgetLayout>,
+}
+
+impl HudLayout {
+    pub fn parse(json: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let json = std::fs::read_to_string(path)?;
+        HudLayout::parse(&json)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e).into())
+    }
+}
+
+impl Default for HudLayout {
+    fn default() -> Self {
+        HudLayout::parse(include_str!("../../assets/hud.json")).expect("Bad built in HUD layout")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_built_in_layout() {
+        let layout = HudLayout::default();
+        assert!(layout.widgets.iter().any(|w| w.kind == WidgetKind::Score));
+    }
+
+    #[test]
+    fn test_defaults() {
+        let layout =
+            HudLayout::parse(r#"{ "widgets": [ { "kind": "wave", "anchor": "bottom" } ] }"#)
+                .unwrap();
+        let wave = &layout.widgets[0];
+        assert_eq!(wave.offset, (0.0, 0.0));
+        assert_eq!(wave.color, Color::WHITE);
+    }
+
+    #[test]
+    fn test_place() {
+        let screen = Screen::new((1200, 600));
+        let size = (100.0, 20.0);
+        assert_eq!(
+            Anchor::TopLeft.place(screen, (10.0, 5.0), size),
+            Position(10.0, 5.0)
+        );
+        assert_eq!(
+            Anchor::TopRight.place(screen, (-10.0, 0.0), size),
+            Position(1090.0, 0.0)
+        );
+        assert_eq!(
+            Anchor::Bottom.place(screen, (0.0, -10.0), size),
+            Position(550.0, 570.0)
+        );
+        assert_eq!(
+            Anchor::Center.place(screen, (0.0, 0.0), size),
+            Position(550.0, 290.0)
+        );
+    }
+}
@@ -0,0 +1,253 @@
+// The status shown on top of the game while playing. Each widget is placed
+// on the screen by the layout and shows one of the resources in the world.
+// Widgets showing resources that aren't in the world aren't drawn.
+mod layout;
+
+use std::error::Error;
+
+use ecs_components::Sprite;
+use geometry::{Rect, RectSize};
+use localization::Strings;
+use render::{Color, FontType, Renderer, SpriteOptions, TextPosition};
+use shared_types::{BossHealth, PlayingGameState, ScoreMultiplier, Wave, WeaponLevel};
+use specs::World;
+
+pub use layout::{Anchor, HudLayout, WidgetKind, WidgetLayout};
+
+// How quickly the shown score catches up with the real score, as the part
+// of the difference covered per second, and the least points per second
+const SCORE_ROLL_RATE: f32 = 8.0;
+const SCORE_ROLL_MIN_SPEED: f32 = 20.0;
+
+// Space between the ship icons showing lives
+const LIFE_ICON_GAP: f32 = 6.0;
+
+pub struct Hud {
+    layout: HudLayout,
+    shown_score: f32,
+}
+
+impl Hud {
+    pub fn new(layout: HudLayout) -> Self {
+        Hud {
+            layout,
+            shown_score: 0.0,
+        }
+    }
+
+    pub fn set_layout(&mut self, layout: HudLayout) {
+        self.layout = layout;
+    }
+
+    // The score as shown, rolling towards the real score
+    pub fn shown_score(&self) -> u32 {
+        self.shown_score.round() as u32
+    }
+
+    pub fn update(&mut self, world: &World, delta_seconds: f32) {
+        let target = world
+            .try_fetch::<PlayingGameState>()
+            .map_or(0, |state| state.score) as f32;
+        if target < self.shown_score {
+            // A new game has started
+            self.shown_score = target;
+            return;
+        }
+        let difference = target - self.shown_score;
+        let step = (difference * SCORE_ROLL_RATE).max(SCORE_ROLL_MIN_SPEED) * delta_seconds;
+        self.shown_score = (self.shown_score + step).min(target);
+    }
+
+    pub fn draw(
+        &self,
+        world: &World,
+        strings: &Strings,
+        renderer: &mut impl Renderer,
+    ) -> Result<(), Box<dyn Error>> {
+        for widget in &self.layout.widgets {
+            match widget.kind {
+                WidgetKind::Score => {
+                    let text = strings.number(self.shown_score().into());
+                    draw_text(renderer, widget, &text)?;
+                }
+                WidgetKind::Lives => {
+                    if let Some(state) = world.try_fetch::<PlayingGameState>() {
+                        draw_lives(renderer, widget, state.lives_left.max(0) as u32)?;
+                    }
+                }
+                WidgetKind::Multiplier => {
+                    if let Some(multiplier) = world.try_fetch::<ScoreMultiplier>() {
+                        draw_gauge(renderer, widget, multiplier.value, multiplier.progress)?;
+                    }
+                }
+                WidgetKind::BossHealth => {
+                    if let Some(health) = world.try_fetch::<BossHealth>() {
+                        draw_bar(renderer, widget, health.0);
+                    }
+                }
+                WidgetKind::WeaponLevel => {
+                    if let Some(level) = world.try_fetch::<WeaponLevel>() {
+                        let level = level.0.to_string();
+                        let text = strings.format("hud.weapon", &[("level", &level)]);
+                        draw_text(renderer, widget, &text)?;
+                    }
+                }
+                WidgetKind::Wave => {
+                    if let Some(wave) = world.try_fetch::<Wave>() {
+                        let wave = wave.0.to_string();
+                        let text = strings.format("hud.wave", &[("wave", &wave)]);
+                        draw_text(renderer, widget, &text)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn draw_text(
+    renderer: &mut impl Renderer,
+    widget: &WidgetLayout,
+    text: &str,
+) -> Result<(), Box<dyn Error>> {
+    let (width, height) = renderer.text_size(text, FontType::Info)?;
+    let topleft = widget.anchor.place(
+        renderer.screen(),
+        widget.offset,
+        (width as f32, height as f32),
+    );
+    renderer.draw_text(
+        text,
+        TextPosition::TopLeft(topleft.0.max(0.0) as u32, topleft.1.max(0.0) as u32),
+        widget.color,
+        FontType::Info,
+    )
+}
+
+fn draw_lives(
+    renderer: &mut impl Renderer,
+    widget: &WidgetLayout,
+    lives: u32,
+) -> Result<(), Box<dyn Error>> {
+    let (icon_width, icon_height) = renderer.sprite_size(&Sprite::Player)?;
+    let scale = widget.size.1 / icon_height as f32;
+    let step = icon_width as f32 * scale + LIFE_ICON_GAP;
+    let width = (step * lives as f32 - LIFE_ICON_GAP).max(0.0);
+    let topleft = widget
+        .anchor
+        .place(renderer.screen(), widget.offset, (width, widget.size.1));
+    let options = SpriteOptions {
+        scale,
+        tint: widget.color,
+        ..SpriteOptions::default()
+    };
+    for life in 0..lives {
+        let position = (topleft.0 + step * life as f32, topleft.1).into();
+        renderer.draw_sprite(&Sprite::Player, position, &options)?;
+    }
+    Ok(())
+}
+
+// A bar filled from the left by the fraction, on a dim background
+fn draw_bar(renderer: &mut impl Renderer, widget: &WidgetLayout, fraction: f32) -> Rect {
+    let topleft = widget
+        .anchor
+        .place(renderer.screen(), widget.offset, widget.size);
+    let background = Color::rgba(widget.color.r, widget.color.g, widget.color.b, 60);
+    renderer.fill_rect(
+        Rect::new(topleft, RectSize(widget.size.0, widget.size.1)),
+        background,
+    );
+    let filled = widget.size.0 * fraction.clamp(0.0, 1.0);
+    renderer.fill_rect(
+        Rect::new(topleft, RectSize(filled, widget.size.1)),
+        widget.color,
+    );
+    Rect::new(topleft, RectSize(widget.size.0, widget.size.1))
+}
+
+// A bar showing the progress towards the next multiplier, with the current
+// multiplier to the right of it
+fn draw_gauge(
+    renderer: &mut impl Renderer,
+    widget: &WidgetLayout,
+    multiplier: u32,
+    progress: f32,
+) -> Result<(), Box<dyn Error>> {
+    let bar = draw_bar(renderer, widget, progress);
+    let text = format!("x{}", multiplier);
+    let (_, height) = renderer.text_size(&text, FontType::Info)?;
+    let top = (bar.center().1 - height as f32 / 2.0).max(0.0);
+    renderer.draw_text(
+        &text,
+        TextPosition::TopLeft((bar.right() + 8.0) as u32, top as u32),
+        widget.color,
+        FontType::Info,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use software_graphics::SoftwareRenderer;
+    use specs::WorldExt;
+    use std::path::Path;
+
+    fn world_with_score(score: u32) -> World {
+        let mut world = World::new();
+        world.insert(PlayingGameState {
+            score,
+            lives_left: 2,
+        });
+        world
+    }
+
+    #[test]
+    fn test_score_rolls() {
+        let mut hud = Hud::new(HudLayout::default());
+        let world = world_with_score(100);
+        hud.update(&world, 0.05);
+        assert!(hud.shown_score() > 0 && hud.shown_score() < 100);
+        for _ in 0..100 {
+            hud.update(&world, 0.05);
+        }
+        assert_eq!(hud.shown_score(), 100);
+
+        hud.update(&world_with_score(3), 0.05);
+        assert_eq!(hud.shown_score(), 3);
+    }
+
+    #[test]
+    fn test_draw() {
+        let assets = Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets");
+        let mut renderer = SoftwareRenderer::with_bundled_font((400, 200), &assets).unwrap();
+        renderer.clear();
+
+        let layout = HudLayout::parse(
+            r#"{ "widgets": [
+                { "kind": "lives", "anchor": "top_left", "size": [0, 20] },
+                { "kind": "boss_health", "anchor": "bottom_right", "size": [100, 10],
+                  "color": { "r": 255, "g": 0, "b": 0 } } ] }"#,
+        )
+        .unwrap();
+        let mut world = world_with_score(0);
+        world.insert(BossHealth(0.5));
+        Hud::new(layout)
+            .draw(&world, &Strings::default(), &mut renderer)
+            .unwrap();
+
+        let image = renderer.image();
+        let drawn = |x: u32, y: u32| image.get_pixel(x, y).0[0..3] != [0, 0, 0];
+        let lives: Vec<_> = image
+            .enumerate_pixels()
+            .filter(|(x, y, p)| *y < 100 && *x < 200 && p.0[0..3] != [0, 0, 0])
+            .map(|(x, y, _)| (x, y))
+            .collect();
+        assert!(!lives.is_empty());
+        assert!(lives.iter().all(|&(_, y)| y < 20));
+        assert_eq!(image.get_pixel(320, 195).0[0..3], [255, 0, 0]);
+        assert!(drawn(380, 195));
+        assert_ne!(image.get_pixel(380, 195).0[0..3], [255, 0, 0]);
+        assert!(!drawn(295, 195));
+    }
+}
@@ -0,0 +1,195 @@
+// The text shown to the player, looked up by message id in a table for
+// the chosen language. Messages missing from a table are shown in English.
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "de")]
+    German,
+    #[serde(rename = "da")]
+    Danish,
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::English, Language::German, Language::Danish];
+
+    // The name of the language in the language itself
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+            Language::Danish => "Dansk",
+        }
+    }
+
+    // The string table in the asset directory
+    pub fn file(&self) -> &'static str {
+        match self {
+            Language::English => "locales/en.json",
+            Language::German => "locales/de.json",
+            Language::Danish => "locales/da.json",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct StringTable(HashMap<String, String>);
+
+impl StringTable {
+    pub fn parse(json: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let json = std::fs::read_to_string(path)?;
+        StringTable::parse(&json)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e).into())
+    }
+
+    pub fn builtin(language: Language) -> Self {
+        let json = match language {
+            Language::English => include_str!("../../assets/locales/en.json"),
+            Language::German => include_str!("../../assets/locales/de.json"),
+            Language::Danish => include_str!("../../assets/locales/da.json"),
+        };
+        StringTable::parse(json).expect("Bad built in string table")
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Strings {
+    language: Language,
+    table: StringTable,
+    english: StringTable,
+}
+
+impl Strings {
+    pub fn new(language: Language, table: StringTable) -> Self {
+        Strings {
+            language,
+            table,
+            english: StringTable::builtin(Language::English),
+        }
+    }
+
+    pub fn builtin(language: Language) -> Self {
+        Strings::new(language, StringTable::builtin(language))
+    }
+
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    pub fn find(&self, id: &str) -> Option<&str> {
+        self.table
+            .0
+            .get(id)
+            .or_else(|| self.english.0.get(id))
+            .map(String::as_str)
+    }
+
+    // A message missing from every table is shown as its id, so it is
+    // easy to spot
+    pub fn get<'s>(&'s self, id: &'s str) -> &'s str {
+        self.find(id).unwrap_or(id)
+    }
+
+    // The message with each {name} replaced by its value
+    pub fn format(&self, id: &str, args: &[(&str, &str)]) -> String {
+        let mut text = self.get(id).to_string();
+        for (name, value) in args {
+            text = text.replace(&format!("{{{}}}", name), value);
+        }
+        text
+    }
+
+    // The number with its digits grouped in thousands
+    pub fn number(&self, number: u64) -> String {
+        let separator = self.get("number.thousands_separator");
+        let digits = number.to_string();
+        let mut text = String::new();
+        for (index, digit) in digits.chars().enumerate() {
+            if index > 0 && (digits.len() - index).is_multiple_of(3) {
+                text.push_str(separator);
+            }
+            text.push(digit);
+        }
+        text
+    }
+
+    // A fraction between 0 and 1 as a whole percentage
+    pub fn percent(&self, fraction: f32) -> String {
+        let value = (fraction * 100.0).round().max(0.0) as u64;
+        self.format("number.percent", &[("value", &self.number(value))])
+    }
+}
+
+impl Default for Strings {
+    fn default() -> Self {
+        Strings::builtin(Language::English)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_tables_have_every_message() {
+        let english = StringTable::builtin(Language::English);
+        for language in Language::ALL.iter() {
+            let table = StringTable::builtin(*language);
+            for id in english.0.keys() {
+                assert!(table.0.contains_key(id), "{:?} lacks {}", language, id);
+            }
+            assert_eq!(table.0.len(), english.0.len(), "{:?}", language);
+        }
+    }
+
+    #[test]
+    fn test_falls_back_to_english() {
+        let table = StringTable::parse(r#"{ "menu.quit": "Beenden" }"#).unwrap();
+        let strings = Strings::new(Language::German, table);
+        assert_eq!(strings.get("menu.quit"), "Beenden");
+        assert_eq!(strings.get("menu.options"), "Options");
+        assert_eq!(strings.get("no.such.message"), "no.such.message");
+        assert_eq!(strings.find("no.such.message"), None);
+    }
+
+    #[test]
+    fn test_format() {
+        let strings = Strings::builtin(Language::Danish);
+        assert_eq!(strings.format("hud.wave", &[("wave", "3")]), "Bølge 3");
+        assert_eq!(
+            Strings::default().format("summary.accuracy_value", &[("hits", "2")]),
+            "{percent} (2 of {shots})"
+        );
+    }
+
+    #[test]
+    fn test_numbers() {
+        let english = Strings::default();
+        assert_eq!(english.number(0), "0");
+        assert_eq!(english.number(999), "999");
+        assert_eq!(english.number(1000), "1,000");
+        assert_eq!(english.number(1234567), "1,234,567");
+        assert_eq!(english.percent(0.456), "46%");
+        let german = Strings::builtin(Language::German);
+        assert_eq!(german.number(123456), "123.456");
+        assert_eq!(german.percent(1.0), "100 %");
+    }
+
+    #[test]
+    fn test_language_names_in_settings() {
+        assert_eq!(serde_json::to_string(&Language::Danish).unwrap(), "\"da\"");
+        let language: Language = serde_json::from_str("\"de\"").unwrap();
+        assert_eq!(language, Language::German);
+    }
+}
@@ -1,126 +1,559 @@
 // TODO: Get rid of the magic numbers
+mod menus;
+mod summary;
+
 use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use asset_manager::Assets;
+use debug_overlay::DebugOverlay;
 
 use geometry::Rect;
-use sdl_graphics::{FontType, Graphics, TextPosition};
-use sdl_input::ControlState;
-use shared_types::{Arena, ElapsedSeconds, GameState, PlayingGameState};
+use hud::{Hud, HudLayout};
+use localization::{Language, StringTable, Strings};
+use menu::{Menu, MenuEvent};
+use menus::MenuItem;
+use render::{
+    sprite_asset_files, Color, FontConfig, FontType, Renderer, SpriteOptions, TextPosition,
+};
+use run_history::{RunHistory, RunRecord};
+use sdl_input::{Control, ControlState, Gamepads, KeyBindings};
+use settings::{KeyNames, Settings};
+use shared_types::{
+    sound_asset_files, Accessibility, Arena, AudioBackend, AudioQueue, Camera, Difficulty,
+    ElapsedSeconds, GameMode, GameState, HighScores, MusicTrack, NullAudio, PlayingGameState,
+    RunStats,
+};
 
-use ecs_components::{Draw, Invincibility, IsPlayer, KeepInside, Position, Sprite, Velocity};
+use ecs_components::{
+    Acceleration, Alpha, Animation, AnimationMode, Blink, Drag, Draw, FadeOut, Invincibility,
+    IsPlayer, KeepInside, Layer, Lifetime, MaxSpeed, Particle, ParticleEffects, Position, Rotation,
+    RotationKind, Sprite, Starfield, Text, Tiled, Tint, Twinkle, Velocity, ZOrder,
+};
 
-use sdl2::pixels::Color;
-use specs::{Builder, Dispatcher, ReadStorage, World, WorldExt};
+use specs::{Builder, Dispatcher, Entities, Read, ReadStorage, World, WorldExt};
 use time::Duration;
 
+// The size of the screen the game is drawn on, no matter the window size
+const LOGICAL_SIZE: (u32, u32) = (1200, 600);
+
+// Space kept free of enemies at the top of the screen for the HUD
+const HUD_HEIGHT: u32 = 32;
+
+// Data files in the asset directory. The built in versions are used when
+// they are missing.
+const STARFIELD_FILE: &str = "starfield.json";
+const PARTICLES_FILE: &str = "particles.json";
+const HUD_FILE: &str = "hud.json";
+const FONTS_FILE: &str = "fonts.json";
+
+// Command line flag naming the asset directory
+const ASSETS_FLAG: &str = "--assets";
+
+// Shows and hides the debug overlay
+const DEBUG_KEY: sdl2::keyboard::Keycode = sdl2::keyboard::Keycode::F3;
+
+// A font file to use for all text instead of the ones in the fonts file
+const FONT_VARIABLE: &str = "RIDEWAYS_FONT";
+
 fn main() -> Result<(), Box<dyn Error>> {
-    let window_size = (1200, 600);
+    let assets_arg = std::env::args()
+        .skip_while(|arg| arg != ASSETS_FLAG)
+        .nth(1)
+        .map(PathBuf::from);
+    let mut assets = Assets::find(assets_arg.as_deref())?;
+    for file in sprite_asset_files() {
+        assets.watch(&file);
+    }
+    for file in sound_asset_files() {
+        assets.watch(file);
+    }
+    assets.watch(STARFIELD_FILE);
+    assets.watch(PARTICLES_FILE);
+    assets.watch(HUD_FILE);
+
+    let settings_path = Settings::path();
+    let mut settings = match &settings_path {
+        Some(path) => Settings::load_or_default(path),
+        None => Settings::default(),
+    };
+    let mut key_bindings = key_bindings_for(&settings.keys);
+    let mut strings = load_strings(&assets, settings.language);
+
     let sdl_contexts = sdl_graphics::Contexts::new()?;
-    let window = sdl_graphics::Graphics::make_window(&sdl_contexts, "Rideways", window_size)?;
+    let window = sdl_graphics::Graphics::make_window(
+        &sdl_contexts,
+        strings.get("game.title"),
+        LOGICAL_SIZE,
+        settings.window_size,
+        settings.vsync,
+    )?;
     let texture_creator = window.canvas.texture_creator();
-    let mut graphics = sdl_graphics::Graphics::new(window, &sdl_contexts, &texture_creator)?;
-    let (mut world, mut dispatcher) = ecs_setup::setup(graphics.entity_sizes()?)?;
+    let mut audio: Box<dyn AudioBackend> =
+        match sdl_audio::Audio::new(sdl_contexts.sdl(), assets.root()) {
+            Ok(audio) => Box::new(audio),
+            Err(e) => {
+                eprintln!("Playing without sound: {}", e);
+                Box::new(NullAudio)
+            }
+        };
+    let mut fonts = load_fonts(&assets)?;
+    if let Some(font_file) = std::env::var_os(FONT_VARIABLE) {
+        fonts.prefer_file(font_file.as_ref());
+    }
+    let mut graphics = sdl_graphics::Graphics::new(
+        window,
+        &sdl_contexts,
+        &texture_creator,
+        assets.root(),
+        &fonts,
+    )?;
+    graphics.set_fullscreen(settings.fullscreen)?;
+    let (mut world, mut dispatcher) = ecs_setup::setup(
+        graphics.entity_sizes()?,
+        load_starfield(&assets)?,
+        load_particle_effects(&assets)?,
+    )?;
+    let screen = graphics.screen();
     let arena = Arena(Rect::new(
-        (0.0, 32.0).into(),
-        (window_size.0, window_size.1 - 32).into(),
+        (0, HUD_HEIGHT).into(),
+        (screen.width, screen.height - HUD_HEIGHT).into(),
     ));
     world.insert(arena);
+    world.insert(Camera::new(screen.size().into()));
+    world.insert(settings.difficulty);
+    world.insert(settings.accessibility);
+    world.insert(strings.clone());
+    let mut hud = Hud::new(load_hud_layout(&assets)?);
+    let mut gamepads = match Gamepads::new(sdl_contexts.sdl()) {
+        Ok(gamepads) => Some(gamepads),
+        Err(e) => {
+            eprintln!("Playing without gamepads: {}", e);
+            None
+        }
+    };
     let mut control_state = ControlState::new();
+    let mut previous_control_state = control_state;
+    audio.set_volumes(settings.volumes);
+    let mut menu = Menu::new(menus::main_menu(&strings));
+    let history = RunHistory::find();
+    let mut high_scores = HighScores::default();
+    if let Some(history) = &history {
+        match history.load() {
+            Ok(records) => {
+                for record in records {
+                    high_scores.add(record.score);
+                }
+            }
+            Err(e) => eprintln!("Failed to read {}: {}", history.path().display(), e),
+        }
+    }
+    let mut debug_overlay = DebugOverlay::new();
 
     let mut state = GameState::new();
     let mut previous_time = time::OffsetDateTime::now_utc();
-    loop {
+    'game: loop {
         let time = time::OffsetDateTime::now_utc();
         let delta_time = time - previous_time;
         previous_time = time;
+        debug_overlay.record_frame(delta_time.as_seconds_f32());
+        reload_changed(
+            &mut assets,
+            &mut world,
+            &mut graphics,
+            audio.as_mut(),
+            &mut hud,
+            delta_time,
+        );
 
-        let event = graphics.event_pump.poll_event();
-        if let Some(event) = event {
+        let mut captured_key = None;
+        let events: Vec<_> = graphics.event_pump.poll_iter().collect();
+        for event in events {
+            if let Some(gamepads) = &mut gamepads {
+                gamepads.update(&event);
+            }
             match event {
-                sdl2::event::Event::Quit { .. } => break,
-                _ => control_state.update(&event),
+                sdl2::event::Event::Quit { .. } => break 'game,
+                sdl2::event::Event::KeyDown {
+                    keycode: Some(sdl2::keyboard::Keycode::F11),
+                    repeat: false,
+                    ..
+                } => {
+                    graphics.toggle_fullscreen()?;
+                    settings.fullscreen = graphics.is_fullscreen();
+                    save_settings(&settings, settings_path.as_deref());
+                }
+                sdl2::event::Event::KeyDown {
+                    keycode: Some(DEBUG_KEY),
+                    repeat: false,
+                    ..
+                } => debug_overlay.toggle(),
+                sdl2::event::Event::KeyDown {
+                    keycode: Some(key),
+                    repeat: false,
+                    ..
+                } if menu.is_capturing() && key != sdl2::keyboard::Keycode::Escape => {
+                    captured_key = Some(key)
+                }
+                _ => control_state.update(&event, &key_bindings),
             }
         }
 
         graphics.clear();
+        // Smooth text for the game and menus. It is cached, so blending
+        // costs little.
+        graphics.set_text_mode(sdl_graphics::TextMode::Blended);
         state = match state {
-            GameState::Idle { button_pressed } => {
-                let state = idle(button_pressed, control_state, &mut graphics)?;
-                if let GameState::Playing { .. } = state {
-                    ecs_setup::initialize_world(&mut world);
+            GameState::Menu => {
+                let mut events: Vec<_> =
+                    menus::menu_actions(&previous_control_state, &control_state)
+                        .into_iter()
+                        .filter_map(|action| menu.handle(action))
+                        .collect();
+                if let Some(key) = captured_key {
+                    events.extend(menu.capture_key(&key.name()));
+                }
+                let old_settings = settings.clone();
+                let mut choice = None;
+                for event in events {
+                    choice = choice.or(handle_menu_event(
+                        event,
+                        &mut menu,
+                        &mut settings,
+                        &strings,
+                        &high_scores,
+                        &mut graphics,
+                        audio.as_mut(),
+                    )?);
+                }
+                if settings != old_settings {
+                    key_bindings = key_bindings_for(&settings.keys);
+                    world.insert(settings.difficulty);
+                    world.insert(settings.accessibility);
+                    save_settings(&settings, settings_path.as_deref());
+                }
+                if settings.language != old_settings.language {
+                    // The menu is made again in the new language
+                    strings = load_strings(&assets, settings.language);
+                    world.insert(strings.clone());
+                    menu = Menu::new(menus::main_menu(&strings));
+                    menu.open(menus::options_screen(&strings, settings.difficulty));
+                    menu.current_mut().select(MenuItem::Language);
+                }
+                menu.draw(&mut graphics, &strings)?;
+                match choice {
+                    Some(MenuChoice::Quit) => break 'game,
+                    Some(MenuChoice::Play(mode)) => {
+                        menu.back_to_root();
+                        ecs_setup::initialize_world(&mut world);
+                        GameState::Playing {
+                            state: PlayingGameState::for_mode(mode),
+                        }
+                    }
+                    None => GameState::Menu,
+                }
+            }
+            GameState::Playing { state } => {
+                let state = play(
+                    state,
+                    &mut world,
+                    &mut dispatcher,
+                    control_state,
+                    delta_time,
+                    &mut hud,
+                    &mut graphics,
+                )?;
+                if let GameState::GameOver { .. } = state {
+                    let record = RunRecord {
+                        ended_at: time.unix_timestamp(),
+                        mode: settings.mode,
+                        difficulty: settings.difficulty,
+                        score: ecs_components::get_playing_state(&world).score,
+                        stats: (*world.read_resource::<RunStats>()).clone(),
+                    };
+                    high_scores.add(record.score);
+                    if let Some(history) = &history {
+                        if let Err(e) = history.append(&record) {
+                            eprintln!("Failed to save the game to the history: {}", e);
+                        }
+                    }
                 }
                 state
             }
-            GameState::Playing { state } => play(
-                state,
-                &mut world,
-                &mut dispatcher,
-                control_state,
-                delta_time,
-                &mut graphics,
-            )?,
             GameState::GameOver { seconds_left } => {
-                game_over(seconds_left, delta_time.as_seconds_f64(), &mut graphics)?
+                let fire_pressed = control_state.fire && !previous_control_state.fire;
+                game_over(
+                    seconds_left,
+                    delta_time.as_seconds_f64(),
+                    fire_pressed,
+                    &world,
+                    &mut graphics,
+                )?
             }
         };
+        // The overlay text changes every frame
+        graphics.set_text_mode(sdl_graphics::TextMode::Solid);
+        debug_overlay.draw(&world, &mut graphics)?;
+        // A sound that fails to play is reported and the game goes on
+        let sounds = world
+            .write_resource::<AudioQueue>()
+            .drain_into(audio.as_mut(), &world.read_resource::<Camera>());
+        for result in [sounds, audio.play_music(music_for(&state))] {
+            if let Err(e) = result {
+                eprintln!("Failed to play audio: {}", e);
+            }
+        }
         graphics.present();
+        previous_control_state = control_state;
     }
 
+    // The window may have been resized by dragging its edges
+    if !graphics.is_fullscreen() && graphics.window_size() != settings.window_size {
+        settings.window_size = graphics.window_size();
+        save_settings(&settings, settings_path.as_deref());
+    }
     Ok(())
 }
 
+fn save_settings(settings: &Settings, path: Option<&Path>) {
+    if let Some(path) = path {
+        if let Err(e) = settings.save(path) {
+            eprintln!("Failed to save the settings to {}: {}", path.display(), e);
+        }
+    }
+}
+
+// Keys with names SDL doesn't know make all the keys go back to the
+// defaults, as the remaining keys might clash with them
+fn key_bindings_for(keys: &KeyNames) -> KeyBindings {
+    let names = [&keys.up, &keys.down, &keys.left, &keys.right, &keys.fire];
+    let mut bindings = KeyBindings::default();
+    for (&control, name) in Control::ALL.iter().zip(names.iter()) {
+        match sdl2::keyboard::Keycode::from_name(name) {
+            Some(key) => bindings.set(control, key),
+            None => {
+                eprintln!("Unknown key {}, using the default keys", name);
+                return KeyBindings::default();
+            }
+        }
+    }
+    bindings
+}
+
+fn key_names_for(bindings: &KeyBindings) -> KeyNames {
+    KeyNames {
+        up: bindings.up.name(),
+        down: bindings.down.name(),
+        left: bindings.left.name(),
+        right: bindings.right.name(),
+        fire: bindings.fire.name(),
+    }
+}
+
+fn load_starfield(assets: &Assets) -> Result<Starfield, Box<dyn Error>> {
+    if assets.exists(STARFIELD_FILE) {
+        Starfield::load(&assets.path(STARFIELD_FILE))
+    } else {
+        Ok(Starfield::default())
+    }
+}
+
+fn load_particle_effects(assets: &Assets) -> Result<ParticleEffects, Box<dyn Error>> {
+    if assets.exists(PARTICLES_FILE) {
+        ParticleEffects::load(&assets.path(PARTICLES_FILE))
+    } else {
+        Ok(ParticleEffects::default())
+    }
+}
+
+// A broken string table is reported and the built in one used instead,
+// so a translation being worked on never keeps the game from starting
+fn load_strings(assets: &Assets, language: Language) -> Strings {
+    let file = language.file();
+    if !assets.exists(file) {
+        return Strings::builtin(language);
+    }
+    match StringTable::load(&assets.path(file)) {
+        Ok(table) => Strings::new(language, table),
+        Err(e) => {
+            eprintln!("{}", e);
+            Strings::builtin(language)
+        }
+    }
+}
+
+fn load_fonts(assets: &Assets) -> Result<FontConfig, Box<dyn Error>> {
+    if assets.exists(FONTS_FILE) {
+        FontConfig::load(&assets.path(FONTS_FILE))
+    } else {
+        Ok(FontConfig::default())
+    }
+}
+
+fn load_hud_layout(assets: &Assets) -> Result<HudLayout, Box<dyn Error>> {
+    if assets.exists(HUD_FILE) {
+        HudLayout::load(&assets.path(HUD_FILE))
+    } else {
+        Ok(HudLayout::default())
+    }
+}
+
+// Loads the assets that have changed on disk again. A file that fails to
+// load is reported and the old version is kept, so a half saved file
+// doesn't stop the game.
+fn reload_changed(
+    assets: &mut Assets,
+    world: &mut World,
+    graphics: &mut impl Renderer,
+    audio: &mut dyn AudioBackend,
+    hud: &mut Hud,
+    delta_time: Duration,
+) {
+    for file in assets.poll(delta_time.as_seconds_f32()) {
+        let result = match file.as_str() {
+            STARFIELD_FILE => load_starfield(assets).map(|starfield| world.insert(starfield)),
+            PARTICLES_FILE => load_particle_effects(assets).map(|effects| world.insert(effects)),
+            HUD_FILE => load_hud_layout(assets).map(|layout| hud.set_layout(layout)),
+            _ => graphics.reload(&file).and_then(|_| audio.reload(&file)),
+        };
+        if let Err(e) = result {
+            eprintln!("Failed to reload {}: {}", file, e);
+        }
+    }
+}
+
+fn music_for(state: &GameState) -> MusicTrack {
+    match state {
+        GameState::Playing { .. } => MusicTrack::Playing,
+        GameState::Menu | GameState::GameOver { .. } => MusicTrack::Title,
+    }
+}
+
+// Shows the summary of the game until fire is pressed, which is ignored
+// for the first moment so a player still firing doesn't skip it
 fn game_over(
     seconds_left: f64,
     seconds_passed: f64,
-    graphics: &mut Graphics,
+    fire_pressed: bool,
+    world: &World,
+    graphics: &mut impl Renderer,
 ) -> Result<GameState, Box<dyn Error>> {
-    graphics.draw_text(
-        "Game Over",
-        TextPosition::Center(600, 300),
-        Color::RGBA(255, 0, 0, 0),
-        FontType::Title,
+    let seconds_left = (seconds_left - seconds_passed).max(0.0);
+    let can_continue = seconds_left == 0.0;
+    summary::draw_summary(
+        graphics,
+        &world.read_resource::<Strings>(),
+        &world.read_resource::<RunStats>(),
+        ecs_components::get_playing_state(world).score,
+        can_continue,
     )?;
 
-    let new_state = if seconds_left > seconds_passed {
-        GameState::GameOver {
-            seconds_left: seconds_left - seconds_passed,
-        }
+    let new_state = if can_continue && fire_pressed {
+        GameState::Menu
     } else {
-        GameState::Idle {
-            button_pressed: false,
-        }
+        GameState::GameOver { seconds_left }
     };
     Ok(new_state)
 }
 
-fn idle(
-    button_pressed: bool,
-    control_state: ControlState,
-    graphics: &mut Graphics,
-) -> Result<GameState, Box<dyn Error>> {
-    graphics.draw_text(
-        "Rideways",
-        TextPosition::Center(600, 300),
-        Color::RGBA(255, 0, 0, 0),
-        FontType::Title,
-    )?;
-    graphics.draw_text(
-        "Press fire to play",
-        TextPosition::Center(600, 500),
-        Color::RGBA(255, 0, 0, 0),
-        FontType::Info,
-    )?;
-    let new_state = if control_state.fire {
-        GameState::Idle {
-            button_pressed: true,
+// What the player chose to do from the menu
+enum MenuChoice {
+    Play(GameMode),
+    Quit,
+}
+
+fn handle_menu_event(
+    event: MenuEvent<MenuItem>,
+    menu: &mut Menu<MenuItem>,
+    settings: &mut Settings,
+    strings: &Strings,
+    high_scores: &HighScores,
+    graphics: &mut sdl_graphics::Graphics,
+    audio: &mut dyn AudioBackend,
+) -> Result<Option<MenuChoice>, Box<dyn Error>> {
+    match event {
+        MenuEvent::Pressed(MenuItem::Start) => return Ok(Some(MenuChoice::Play(settings.mode))),
+        MenuEvent::Pressed(MenuItem::Mode(mode)) => {
+            settings.mode = mode;
+            return Ok(Some(MenuChoice::Play(mode)));
         }
-    } else if button_pressed {
-        GameState::Playing {
-            state: PlayingGameState::new(),
+        MenuEvent::Pressed(MenuItem::Quit) => return Ok(Some(MenuChoice::Quit)),
+        MenuEvent::Pressed(MenuItem::ModeSelect) => {
+            menu.open(menus::mode_screen(strings, settings.mode))
         }
-    } else {
-        GameState::Idle { button_pressed }
-    };
-    Ok(new_state)
+        MenuEvent::Pressed(MenuItem::Options) => {
+            menu.open(menus::options_screen(strings, settings.difficulty))
+        }
+        MenuEvent::Pressed(MenuItem::Sound) => {
+            menu.open(menus::sound_screen(strings, settings.volumes))
+        }
+        MenuEvent::Pressed(MenuItem::Display) => menu.open(menus::display_screen(
+            strings,
+            graphics.is_fullscreen(),
+            graphics.window_size(),
+            settings.vsync,
+        )),
+        MenuEvent::Pressed(MenuItem::Accessibility) => {
+            menu.open(menus::accessibility_screen(strings, settings.accessibility))
+        }
+        MenuEvent::Pressed(MenuItem::HighScores) => {
+            menu.open(menus::high_scores_screen(strings, high_scores))
+        }
+        MenuEvent::Pressed(MenuItem::Controls) => menu.open(menus::controls_screen(
+            strings,
+            &key_bindings_for(&settings.keys),
+        )),
+        MenuEvent::Pressed(MenuItem::Back) => {
+            menu.back();
+        }
+        MenuEvent::Slid(item, value) => {
+            match item {
+                MenuItem::MasterVolume => settings.volumes.master = value,
+                MenuItem::MusicVolume => settings.volumes.music = value,
+                MenuItem::EffectsVolume => settings.volumes.effects = value,
+                _ => {}
+            }
+            audio.set_volumes(settings.volumes);
+        }
+        MenuEvent::Chosen(MenuItem::Difficulty, index) => {
+            settings.difficulty = Difficulty::ALL[index];
+        }
+        MenuEvent::Chosen(MenuItem::Language, index) => {
+            settings.language = Language::ALL[index];
+        }
+        MenuEvent::Chosen(MenuItem::WindowSize(shown_for), index) => {
+            let size = menus::window_sizes(shown_for)[index];
+            graphics.set_window_size(size)?;
+            settings.window_size = size;
+        }
+        MenuEvent::Toggled(MenuItem::Fullscreen, on) => {
+            graphics.set_fullscreen(on)?;
+            settings.fullscreen = on;
+        }
+        MenuEvent::Toggled(MenuItem::Vsync, on) => settings.vsync = on,
+        MenuEvent::Toggled(MenuItem::ScreenShake, on) => settings.accessibility.screen_shake = on,
+        MenuEvent::Toggled(MenuItem::ReduceFlashing, on) => {
+            settings.accessibility.reduce_flashing = on
+        }
+        MenuEvent::KeyCaptured(MenuItem::Bind(control), name) => {
+            if let Some(key) = sdl2::keyboard::Keycode::from_name(&name) {
+                // A key used by another control is swapped with this one
+                let mut bindings = key_bindings_for(&settings.keys);
+                let old_key = bindings.key(control);
+                for &other in Control::ALL.iter() {
+                    if bindings.key(other) == key {
+                        bindings.set(other, old_key);
+                    }
+                }
+                bindings.set(control, key);
+                settings.keys = key_names_for(&bindings);
+                *menu.current_mut() = menus::controls_screen(strings, &bindings);
+                menu.current_mut().select(MenuItem::Bind(control));
+            }
+        }
+        _ => {}
+    }
+    Ok(None)
 }
 
 fn play(
@@ -129,15 +562,9 @@ fn play(
     dispatcher: &mut Dispatcher,
     control_state: ControlState,
     delta_time: Duration,
-    graphics: &mut Graphics,
+    hud: &mut Hud,
+    graphics: &mut impl Renderer,
 ) -> Result<GameState, Box<dyn Error>> {
-    let status_text = format!("Lives: {}   Score: {}", state.lives_left, state.score);
-    graphics.draw_text(
-        &status_text,
-        TextPosition::TopRight(1200, 0),
-        Color::RGB(255, 255, 255),
-        FontType::Info,
-    )?;
     world.insert(control_state);
     world.insert(ElapsedSeconds(delta_time.as_seconds_f32()));
     world.insert(state);
@@ -146,49 +573,228 @@ fn play(
     dispatcher.dispatch(world);
     draw_world(world, graphics)?;
 
+    hud.update(world, delta_time.as_seconds_f32());
+    hud.draw(world, &world.read_resource::<Strings>(), graphics)?;
+
     let mut state = ecs_components::get_playing_state(world);
     let is_player_dead = ecs_components::is_player_dead(world);
     let all_explosions_gone = ecs_components::all_explosions_gone(world);
 
     if is_player_dead && all_explosions_gone && !state.any_lives_left() {
-        Ok(GameState::GameOver { seconds_left: 2.0 })
+        Ok(GameState::GameOver { seconds_left: 1.0 })
     } else {
         if is_player_dead && all_explosions_gone {
             state.one_dead();
+            let spawn_y = world.read_resource::<Arena>().0.center().1;
+            let player_size = graphics.entity_sizes()?.player_size;
             world
                 .create_entity()
                 .with(Position {
-                    rect: Rect::new((0, 300).into(), graphics.entity_sizes()?.player_size.into()),
+                    rect: Rect::new(
+                        (0.0, spawn_y - player_size.1 as f32 / 2.0).into(),
+                        player_size.into(),
+                    ),
                 })
                 .with(Velocity { x: 0.0, y: 0.0 })
-                .with(Sprite::PlayerGhost)
+                .with(Acceleration::default())
+                .with(Drag { rate: 6.0 })
+                .with(MaxSpeed { speed: 400.0 })
+                .with(Rotation::default())
+                .with(RotationKind::Bank {
+                    max_degrees: 12.0,
+                    full_speed: 400.0,
+                })
+                .with(Sprite::Player)
+                .with(Animation::uniform(&[0, 1], 0.06, AnimationMode::Loop))
+                .with(Layer::Player)
                 .with(IsPlayer)
                 .with(KeepInside)
                 .with(Invincibility { seconds_left: 5.0 })
+                .with(Blink::new(0.1))
                 .build();
         }
         Ok(GameState::Playing { state })
     }
 }
 
-fn draw_world(world: &World, graphics: &mut Graphics<'_>) -> Result<(), Box<dyn Error>> {
-    type DrawSystemData<'a> = (ReadStorage<'a, Position>, ReadStorage<'a, Draw>);
-    let (positions, drawables): DrawSystemData = world.system_data();
-    for (position, drawable) in (&positions, &drawables).join() {
-        match drawable {
-            Draw::Star(radius) => {
-                let pos = position.rect.center();
-                graphics.draw_circle(pos.0, pos.1, *radius);
+enum DrawItem<'a> {
+    Star(geometry::Position, i16, Color),
+    Tiled(&'a Sprite, geometry::Position, SpriteOptions),
+    Sprite(&'a Sprite, geometry::Position, SpriteOptions),
+    Text(&'a str, geometry::Position, Color),
+}
+
+// Draws everything in the world as seen by the camera, ordered by layer,
+// then by z order
+fn draw_world(world: &World, graphics: &mut impl Renderer) -> Result<(), Box<dyn Error>> {
+    use specs::Join;
+    type DrawSystemData<'a> = (
+        Read<'a, Camera>,
+        Read<'a, Accessibility>,
+        Entities<'a>,
+        ReadStorage<'a, Position>,
+        ReadStorage<'a, Draw>,
+        ReadStorage<'a, Sprite>,
+        ReadStorage<'a, Rotation>,
+        ReadStorage<'a, Animation>,
+        ReadStorage<'a, Layer>,
+        ReadStorage<'a, ZOrder>,
+        ReadStorage<'a, Twinkle>,
+        ReadStorage<'a, Tiled>,
+        ReadStorage<'a, Particle>,
+        ReadStorage<'a, Text>,
+        ReadStorage<'a, FadeOut>,
+        ReadStorage<'a, Lifetime>,
+        ReadStorage<'a, Tint>,
+        ReadStorage<'a, Alpha>,
+        ReadStorage<'a, Blink>,
+    );
+    let (
+        camera,
+        accessibility,
+        entities,
+        positions,
+        drawables,
+        sprites,
+        rotations,
+        animations,
+        layers,
+        z_orders,
+        twinkles,
+        tiled,
+        particles,
+        texts,
+        fade_outs,
+        lifetimes,
+        tints,
+        alphas,
+        blinks,
+    ): DrawSystemData = world.system_data();
+
+    let mut items = vec![];
+    for (
+        entity,
+        position,
+        drawable,
+        sprite,
+        rotation,
+        animation,
+        layer,
+        z_order,
+        twinkle,
+        tiled,
+        particle,
+        text,
+        (fade_out, lifetime, tint, alpha, blink),
+    ) in (
+        &entities,
+        &positions,
+        drawables.maybe(),
+        sprites.maybe(),
+        rotations.maybe(),
+        animations.maybe(),
+        layers.maybe(),
+        z_orders.maybe(),
+        twinkles.maybe(),
+        tiled.maybe(),
+        particles.maybe(),
+        texts.maybe(),
+        (
+            fade_outs.maybe(),
+            lifetimes.maybe(),
+            tints.maybe(),
+            alphas.maybe(),
+            blinks.maybe(),
+        ),
+    )
+        .join()
+    {
+        let order = (
+            layer.copied().unwrap_or_default(),
+            z_order.copied().unwrap_or_default(),
+            entity.id(),
+        );
+        let fade = match (fade_out, lifetime) {
+            (Some(fade_out), Some(lifetime)) => fade_out.opacity(lifetime),
+            _ => 1.0,
+        };
+        let tint_color = tint.map_or(Color::WHITE, |t| t.0);
+        let opacity = alpha.map_or(1.0, |a| a.0)
+            * fade
+            * blink.map_or(1.0, |b| b.opacity(accessibility.reduce_flashing));
+        if let Some(Draw::Star { radius, color }) = drawable {
+            let center = camera.world_to_screen(position.rect.center());
+            let radius = (*radius as f32 * camera.zoom).round() as i16;
+            let twinkle = twinkle.filter(|_| !accessibility.reduce_flashing);
+            let color = color.scaled(twinkle.map_or(1.0, |t| t.brightness()));
+            items.push((order, DrawItem::Star(center, radius, color)));
+        }
+        if let Some(sprite) = sprite {
+            let options = SpriteOptions {
+                rotation: rotation.copied(),
+                frame: animation.map_or(0, |a| a.frame_index()),
+                scale: camera.zoom * particle.map_or(1.0, |p| p.scale),
+                tint: particle
+                    .map_or(Color::WHITE, |p| p.color)
+                    .tinted(tint_color)
+                    .faded(opacity),
+            };
+            let topleft = if particle.is_some() {
+                // Particles are scaled around their center, so they stay
+                // on the path they were sent along as they grow or shrink
+                let (width, height) = graphics.sprite_size(sprite)?;
+                let center = camera.world_to_screen(position.rect.center());
+                geometry::Position(
+                    center.0 - width as f32 * options.scale / 2.0,
+                    center.1 - height as f32 * options.scale / 2.0,
+                )
+            } else {
+                camera.world_to_screen(position.rect.topleft())
+            };
+            if tiled.is_some() {
+                items.push((order, DrawItem::Tiled(sprite, topleft, options)));
+            } else {
+                items.push((order, DrawItem::Sprite(sprite, topleft, options)));
             }
         }
+        if let Some(text) = text {
+            let center = camera.world_to_screen(position.rect.center());
+            let color = text.color.tinted(tint_color).faded(opacity);
+            items.push((order, DrawItem::Text(&text.text, center, color)));
+        }
     }
-    graphics.back_to_black();
+    items.sort_by_key(|(order, _)| *order);
 
-    use specs::Join;
-    type SpriteSystemData<'a> = (ReadStorage<'a, Position>, ReadStorage<'a, Sprite>);
-    let (positions, sprites): SpriteSystemData = world.system_data();
-    for (position, sprite) in (&positions, &sprites).join() {
-        graphics.draw_sprite(position, sprite)?;
+    for (_, item) in items {
+        match item {
+            DrawItem::Star(center, radius, color) => {
+                graphics.draw_circle(center.0, center.1, radius, color);
+            }
+            DrawItem::Tiled(sprite, topleft, options) => {
+                // Repeat the sprite horizontally so it covers the whole screen
+                let width = graphics.sprite_size(sprite)?.0 as f32 * options.scale;
+                let screen_width = graphics.screen().width as f32;
+                let mut x = topleft.0.rem_euclid(width) - width;
+                while x < screen_width {
+                    graphics.draw_sprite(sprite, (x, topleft.1).into(), &options)?;
+                    x += width;
+                }
+            }
+            DrawItem::Sprite(sprite, topleft, options) => {
+                graphics.draw_sprite(sprite, topleft, &options)?;
+            }
+            DrawItem::Text(text, center, color) => {
+                let screen = graphics.screen();
+                if center.0 >= 0.0
+                    && center.1 >= 0.0
+                    && center.0 < screen.width as f32
+                    && center.1 < screen.height as f32
+                {
+                    let position = TextPosition::Center(center.0 as u32, center.1 as u32);
+                    graphics.draw_text(text, position, color, FontType::Info)?;
+                }
+            }
+        }
     }
 
     Ok(())
@@ -0,0 +1,287 @@
+// The screens of the menu shown when not playing
+use localization::{Language, Strings};
+use menu::{Item, MenuAction, Screen};
+use sdl_input::{Control, ControlState, KeyBindings};
+use shared_types::{Accessibility, Difficulty, GameMode, HighScores, Volumes};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MenuItem {
+    Start,
+    ModeSelect,
+    Options,
+    HighScores,
+    Controls,
+    Quit,
+    Mode(GameMode),
+    Sound,
+    Display,
+    Difficulty,
+    Accessibility,
+    Language,
+    MasterVolume,
+    MusicVolume,
+    EffectsVolume,
+    Fullscreen,
+    // The window size the list of sizes was made for, so choices index the
+    // list that was shown even after the window size changes
+    WindowSize((u32, u32)),
+    Vsync,
+    ScreenShake,
+    ReduceFlashing,
+    Bind(Control),
+    Text,
+    Back,
+}
+
+pub fn main_menu(strings: &Strings) -> Screen<MenuItem> {
+    Screen::new(
+        strings.get("game.title"),
+        vec![
+            Item::button(MenuItem::Start, strings.get("menu.start")),
+            Item::button(MenuItem::ModeSelect, strings.get("menu.mode_select")),
+            Item::button(MenuItem::Options, strings.get("menu.options")),
+            Item::button(MenuItem::HighScores, strings.get("menu.high_scores")),
+            Item::button(MenuItem::Controls, strings.get("menu.controls")),
+            Item::button(MenuItem::Quit, strings.get("menu.quit")),
+        ],
+    )
+}
+
+fn mode_name(strings: &Strings, mode: GameMode) -> &str {
+    match mode {
+        GameMode::Normal => strings.get("mode.normal"),
+        GameMode::Hardcore => strings.get("mode.hardcore"),
+    }
+}
+
+fn difficulty_name(strings: &Strings, difficulty: Difficulty) -> &str {
+    match difficulty {
+        Difficulty::Easy => strings.get("difficulty.easy"),
+        Difficulty::Normal => strings.get("difficulty.normal"),
+        Difficulty::Hard => strings.get("difficulty.hard"),
+    }
+}
+
+pub fn mode_screen(strings: &Strings, current: GameMode) -> Screen<MenuItem> {
+    let mut items: Vec<_> = GameMode::ALL
+        .iter()
+        .map(|&mode| Item::button(MenuItem::Mode(mode), mode_name(strings, mode)))
+        .collect();
+    items.push(Item::button(MenuItem::Back, strings.get("menu.back")));
+    let mut screen = Screen::new(strings.get("menu.mode"), items);
+    screen.select(MenuItem::Mode(current));
+    screen
+}
+
+// The window sizes offered in the display options
+const WINDOW_SIZES: [(u32, u32); 3] = [(1200, 600), (1800, 900), (2400, 1200)];
+
+pub fn options_screen(strings: &Strings, difficulty: Difficulty) -> Screen<MenuItem> {
+    let difficulties = Difficulty::ALL
+        .iter()
+        .map(|&difficulty| difficulty_name(strings, difficulty).to_string())
+        .collect();
+    let selected_difficulty = Difficulty::ALL
+        .iter()
+        .position(|&d| d == difficulty)
+        .unwrap_or(0);
+    // Each language is named in itself, so it can be found from any other
+    let languages = Language::ALL
+        .iter()
+        .map(|language| language.name().to_string())
+        .collect();
+    let selected_language = Language::ALL
+        .iter()
+        .position(|&l| l == strings.language())
+        .unwrap_or(0);
+    Screen::new(
+        strings.get("menu.options"),
+        vec![
+            Item::button(MenuItem::Sound, strings.get("menu.sound")),
+            Item::button(MenuItem::Display, strings.get("menu.display")),
+            Item::list(
+                MenuItem::Difficulty,
+                strings.get("menu.difficulty"),
+                difficulties,
+                selected_difficulty,
+            ),
+            Item::button(MenuItem::Accessibility, strings.get("menu.accessibility")),
+            Item::list(
+                MenuItem::Language,
+                strings.get("menu.language"),
+                languages,
+                selected_language,
+            ),
+            Item::button(MenuItem::Back, strings.get("menu.back")),
+        ],
+    )
+}
+
+pub fn sound_screen(strings: &Strings, volumes: Volumes) -> Screen<MenuItem> {
+    Screen::new(
+        strings.get("menu.sound"),
+        vec![
+            Item::slider(
+                MenuItem::MasterVolume,
+                strings.get("menu.volume"),
+                volumes.master,
+            ),
+            Item::slider(
+                MenuItem::MusicVolume,
+                strings.get("menu.music"),
+                volumes.music,
+            ),
+            Item::slider(
+                MenuItem::EffectsVolume,
+                strings.get("menu.effects"),
+                volumes.effects,
+            ),
+            Item::button(MenuItem::Back, strings.get("menu.back")),
+        ],
+    )
+}
+
+// The window sizes to choose from, with the current size first if it
+// isn't one of the usual ones
+pub fn window_sizes(current: (u32, u32)) -> Vec<(u32, u32)> {
+    let mut sizes = WINDOW_SIZES.to_vec();
+    if !sizes.contains(&current) {
+        sizes.insert(0, current);
+    }
+    sizes
+}
+
+pub fn display_screen(
+    strings: &Strings,
+    fullscreen: bool,
+    window_size: (u32, u32),
+    vsync: bool,
+) -> Screen<MenuItem> {
+    let sizes = window_sizes(window_size);
+    let selected = sizes
+        .iter()
+        .position(|&size| size == window_size)
+        .unwrap_or(0);
+    let size_names = sizes
+        .iter()
+        .map(|(width, height)| format!("{}x{}", width, height))
+        .collect();
+    Screen::new(
+        strings.get("menu.display"),
+        vec![
+            Item::toggle(
+                MenuItem::Fullscreen,
+                strings.get("menu.fullscreen"),
+                fullscreen,
+            ),
+            Item::list(
+                MenuItem::WindowSize(window_size),
+                strings.get("menu.window"),
+                size_names,
+                selected,
+            ),
+            Item::toggle(MenuItem::Vsync, strings.get("menu.vsync"), vsync),
+            Item::label(MenuItem::Text, strings.get("menu.vsync_note")),
+            Item::button(MenuItem::Back, strings.get("menu.back")),
+        ],
+    )
+}
+
+pub fn accessibility_screen(strings: &Strings, accessibility: Accessibility) -> Screen<MenuItem> {
+    Screen::new(
+        strings.get("menu.accessibility"),
+        vec![
+            Item::toggle(
+                MenuItem::ScreenShake,
+                strings.get("menu.screen_shake"),
+                accessibility.screen_shake,
+            ),
+            Item::toggle(
+                MenuItem::ReduceFlashing,
+                strings.get("menu.reduce_flashing"),
+                accessibility.reduce_flashing,
+            ),
+            Item::button(MenuItem::Back, strings.get("menu.back")),
+        ],
+    )
+}
+
+pub fn high_scores_screen(strings: &Strings, high_scores: &HighScores) -> Screen<MenuItem> {
+    let mut items: Vec<_> = high_scores
+        .scores()
+        .iter()
+        .enumerate()
+        .map(|(place, score)| Item::label(MenuItem::Text, &format!("{}.  {:06}", place + 1, score)))
+        .collect();
+    if items.is_empty() {
+        items.push(Item::label(MenuItem::Text, strings.get("menu.no_scores")));
+    }
+    items.push(Item::button(MenuItem::Back, strings.get("menu.back")));
+    Screen::new(strings.get("menu.high_scores"), items)
+}
+
+pub fn controls_screen(strings: &Strings, key_bindings: &KeyBindings) -> Screen<MenuItem> {
+    let mut items: Vec<_> = Control::ALL
+        .iter()
+        .map(|&control| {
+            Item::key_capture(
+                MenuItem::Bind(control),
+                control_name(strings, control),
+                &key_bindings.key(control).name(),
+            )
+        })
+        .collect();
+    items.push(Item::button(MenuItem::Back, strings.get("menu.back")));
+    Screen::new(strings.get("menu.controls"), items)
+}
+
+fn control_name(strings: &Strings, control: Control) -> &str {
+    match control {
+        Control::Up => strings.get("control.up"),
+        Control::Down => strings.get("control.down"),
+        Control::Left => strings.get("control.left"),
+        Control::Right => strings.get("control.right"),
+        Control::Fire => strings.get("control.fire"),
+    }
+}
+
+// The controls pressed since the last frame, as menu actions
+pub fn menu_actions(previous: &ControlState, current: &ControlState) -> Vec<MenuAction> {
+    [
+        (previous.up, current.up, MenuAction::Up),
+        (previous.down, current.down, MenuAction::Down),
+        (previous.left, current.left, MenuAction::Left),
+        (previous.right, current.right, MenuAction::Right),
+        (previous.fire, current.fire, MenuAction::Select),
+        (previous.back, current.back, MenuAction::Back),
+    ]
+    .iter()
+    .filter(|(was_held, is_held, _)| !was_held && *is_held)
+    .map(|(_, _, action)| *action)
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use menu::{Menu, MenuEvent};
+
+    #[test]
+    fn test_choosing_from_a_custom_window_size() {
+        let strings = Strings::default();
+        let mut menu = Menu::new(display_screen(&strings, false, (1000, 500), true));
+        menu.current_mut().select(MenuItem::WindowSize((1000, 500)));
+        let chosen: Vec<_> = (0..4)
+            .map(|_| match menu.handle(MenuAction::Right) {
+                Some(MenuEvent::Chosen(MenuItem::WindowSize(shown_for), index)) => {
+                    window_sizes(shown_for)[index]
+                }
+                event => panic!("Unexpected {:?}", event),
+            })
+            .collect();
+        assert_eq!(
+            chosen,
+            vec![(1200, 600), (1800, 900), (2400, 1200), (1000, 500)]
+        );
+    }
+}
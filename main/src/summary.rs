@@ -0,0 +1,102 @@
+// The screen summing up a game when it is over
+use std::error::Error;
+
+use localization::Strings;
+use render::{Color, FontType, Renderer, TextPosition};
+use shared_types::RunStats;
+
+const TITLE_COLOR: Color = Color::rgb(255, 0, 0);
+const LABEL_COLOR: Color = Color::rgb(150, 150, 150);
+const VALUE_COLOR: Color = Color::WHITE;
+
+// Where the title and the first line are drawn, as parts of the screen
+// height, and where the prompt to continue is drawn
+const TITLE_Y: f32 = 0.15;
+const LINES_Y: f32 = 0.3;
+const PROMPT_Y: f32 = 0.9;
+
+// Space between the labels and the values
+const VALUE_GAP: f32 = 20.0;
+
+fn summary_lines<'s>(strings: &'s Strings, stats: &RunStats, score: u32) -> Vec<(&'s str, String)> {
+    let seconds = stats.seconds_survived as u32;
+    let kills = if stats.aliens_killed.is_empty() {
+        strings.get("summary.no_kills").to_string()
+    } else {
+        stats
+            .aliens_killed
+            .iter()
+            .map(|(kind, count)| {
+                let name = strings.find(&format!("alien.{}", kind)).unwrap_or(kind);
+                format!("{} {}", name, strings.number((*count).into()))
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let accuracy = strings.format(
+        "summary.accuracy_value",
+        &[
+            ("percent", &strings.percent(stats.accuracy())),
+            ("hits", &strings.number(stats.hits.into())),
+            ("shots", &strings.number(stats.shots_fired.into())),
+        ],
+    );
+    vec![
+        (strings.get("summary.score"), strings.number(score.into())),
+        (
+            strings.get("summary.time"),
+            format!("{}:{:02}", seconds / 60, seconds % 60),
+        ),
+        (strings.get("summary.accuracy"), accuracy),
+        (strings.get("summary.aliens_killed"), kills),
+        (
+            strings.get("summary.deaths"),
+            strings.number(stats.deaths.into()),
+        ),
+    ]
+}
+
+pub fn draw_summary(
+    graphics: &mut impl Renderer,
+    strings: &Strings,
+    stats: &RunStats,
+    score: u32,
+    show_prompt: bool,
+) -> Result<(), Box<dyn Error>> {
+    let screen = graphics.screen();
+    let center_x = screen.width / 2;
+    let at_height = |part: f32| (screen.height as f32 * part) as u32;
+    graphics.draw_text(
+        strings.get("summary.title"),
+        TextPosition::Center(center_x, at_height(TITLE_Y)),
+        TITLE_COLOR,
+        FontType::Title,
+    )?;
+
+    let (_, line_height) = graphics.text_size("Ag", FontType::Info)?;
+    for (index, (label, value)) in summary_lines(strings, stats, score).iter().enumerate() {
+        let top = at_height(LINES_Y) + line_height * index as u32;
+        graphics.draw_text(
+            label,
+            TextPosition::TopRight(center_x - VALUE_GAP as u32, top),
+            LABEL_COLOR,
+            FontType::Info,
+        )?;
+        graphics.draw_text(
+            value,
+            TextPosition::TopLeft(center_x + VALUE_GAP as u32, top),
+            VALUE_COLOR,
+            FontType::Info,
+        )?;
+    }
+
+    if show_prompt {
+        graphics.draw_text(
+            strings.get("summary.prompt"),
+            TextPosition::Center(center_x, at_height(PROMPT_Y)),
+            TITLE_COLOR,
+            FontType::Info,
+        )?;
+    }
+    Ok(())
+}
@@ -0,0 +1,351 @@
+// Menus made of screens of widgets, navigated with up, down, left, right,
+// select and back. Screens are opened on top of each other, and back
+// returns to the one below. The game builds the screens and reacts to the
+// events the menu returns.
+mod screen;
+mod widget;
+
+use std::error::Error;
+
+use geometry::{Rect, RectSize};
+use localization::Strings;
+use render::{Color, FontType, Renderer, TextPosition};
+
+pub use screen::Screen;
+pub use widget::{Item, Widget, SLIDER_STEP};
+
+const TITLE_COLOR: Color = Color::rgb(255, 0, 0);
+const ITEM_COLOR: Color = Color::WHITE;
+const SELECTED_COLOR: Color = Color::rgb(255, 210, 0);
+const LABEL_COLOR: Color = Color::rgb(150, 150, 150);
+
+// Where the title and the first item are drawn, as parts of the screen
+// height
+const TITLE_Y: f32 = 0.18;
+const ITEMS_Y: f32 = 0.36;
+
+// Height of each item as a multiple of the height of a line of text
+const ROW_SPACING: f32 = 1.3;
+
+// Space between the label and the value of an item
+const VALUE_GAP: f32 = 24.0;
+
+const SLIDER_SIZE: (f32, f32) = (240.0, 14.0);
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MenuAction {
+    Up,
+    Down,
+    Left,
+    Right,
+    Select,
+    Back,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MenuEvent<Id> {
+    Pressed(Id),
+    Chosen(Id, usize),
+    Slid(Id, f32),
+    Toggled(Id, bool),
+    KeyCaptured(Id, String),
+    // The top screen was closed by going back
+    Closed,
+}
+
+pub struct Menu<Id> {
+    // The bottom screen is never closed
+    screens: Vec<Screen<Id>>,
+}
+
+impl<Id: Copy> Menu<Id> {
+    pub fn new(root: Screen<Id>) -> Self {
+        Menu {
+            screens: vec![root],
+        }
+    }
+
+    pub fn current(&self) -> &Screen<Id> {
+        self.screens.last().expect("a menu always has a screen")
+    }
+
+    pub fn current_mut(&mut self) -> &mut Screen<Id> {
+        self.screens.last_mut().expect("a menu always has a screen")
+    }
+
+    pub fn open(&mut self, screen: Screen<Id>) {
+        self.screens.push(screen);
+    }
+
+    // Closes the top screen, unless it is the only one
+    pub fn back(&mut self) -> bool {
+        if self.screens.len() > 1 {
+            self.screens.pop();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn back_to_root(&mut self) {
+        self.screens.truncate(1);
+    }
+
+    // While a key capture widget is waiting, the game should send the next
+    // key pressed to capture_key instead of treating it as an action
+    pub fn is_capturing(&self) -> bool {
+        self.current().is_capturing()
+    }
+
+    pub fn handle(&mut self, action: MenuAction) -> Option<MenuEvent<Id>> {
+        if self.is_capturing() {
+            if action == MenuAction::Back {
+                self.current_mut().cancel_capture();
+            }
+            None
+        } else if action == MenuAction::Back {
+            if self.back() {
+                Some(MenuEvent::Closed)
+            } else {
+                None
+            }
+        } else {
+            self.current_mut().handle(action)
+        }
+    }
+
+    pub fn capture_key(&mut self, name: &str) -> Option<MenuEvent<Id>> {
+        self.current_mut().capture_key(name)
+    }
+
+    pub fn draw(
+        &self,
+        renderer: &mut impl Renderer,
+        strings: &Strings,
+    ) -> Result<(), Box<dyn Error>> {
+        let screen = renderer.screen();
+        let center_x = screen.width as f32 / 2.0;
+        let current = self.current();
+        renderer.draw_text(
+            &current.title,
+            TextPosition::Center(center_x as u32, (screen.height as f32 * TITLE_Y) as u32),
+            TITLE_COLOR,
+            FontType::Title,
+        )?;
+
+        let (_, line_height) = renderer.text_size("Ag", FontType::Info)?;
+        let row_height = line_height as f32 * ROW_SPACING;
+        let first_top = screen.height as f32 * ITEMS_Y;
+        for (index, item) in current.items.iter().enumerate() {
+            let top = first_top + row_height * index as f32;
+            let text_top = (top + (row_height - line_height as f32) / 2.0) as u32;
+            let color = if !item.selectable() {
+                LABEL_COLOR
+            } else if index == current.selected_index() {
+                SELECTED_COLOR
+            } else {
+                ITEM_COLOR
+            };
+            match (&item.widget, item.value_text(strings)) {
+                (Widget::Slider(value), _) => {
+                    draw_label(renderer, &item.label, center_x, text_top, color)?;
+                    let bar_top = top + (row_height - SLIDER_SIZE.1) / 2.0;
+                    draw_slider(renderer, center_x + VALUE_GAP, bar_top, *value, color);
+                }
+                (_, Some(value)) => {
+                    draw_label(renderer, &item.label, center_x, text_top, color)?;
+                    renderer.draw_text(
+                        &value,
+                        TextPosition::TopLeft((center_x + VALUE_GAP) as u32, text_top),
+                        color,
+                        FontType::Info,
+                    )?;
+                }
+                (_, None) => renderer.draw_text(
+                    &item.label,
+                    TextPosition::Center(center_x as u32, (top + row_height / 2.0) as u32),
+                    color,
+                    FontType::Info,
+                )?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// Draws a label ending just left of the middle of the screen
+fn draw_label(
+    renderer: &mut impl Renderer,
+    label: &str,
+    center_x: f32,
+    top: u32,
+    color: Color,
+) -> Result<(), Box<dyn Error>> {
+    renderer.draw_text(
+        label,
+        TextPosition::TopRight((center_x - VALUE_GAP) as u32, top),
+        color,
+        FontType::Info,
+    )
+}
+
+fn draw_slider(renderer: &mut impl Renderer, left: f32, top: f32, value: f32, color: Color) {
+    let background = Color::rgba(color.r, color.g, color.b, 60);
+    let topleft = (left, top).into();
+    renderer.fill_rect(
+        Rect::new(topleft, RectSize(SLIDER_SIZE.0, SLIDER_SIZE.1)),
+        background,
+    );
+    renderer.fill_rect(
+        Rect::new(topleft, RectSize(SLIDER_SIZE.0 * value, SLIDER_SIZE.1)),
+        color,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use software_graphics::SoftwareRenderer;
+    use std::path::Path;
+
+    fn options() -> Screen<u32> {
+        Screen::new(
+            "Options",
+            vec![
+                Item::label(0, "Sound"),
+                Item::slider(1, "Volume", 0.5),
+                Item::toggle(2, "Fullscreen", false),
+                Item::list(3, "Mode", vec!["Easy".to_string(), "Hard".to_string()], 0),
+                Item::key_capture(4, "Fire", "Return"),
+                Item::button(5, "Back"),
+            ],
+        )
+    }
+
+    fn selected_id(menu: &Menu<u32>) -> u32 {
+        menu.current().selected().unwrap().id
+    }
+
+    #[test]
+    fn test_navigation_skips_labels() {
+        let mut menu = Menu::new(options());
+        assert_eq!(selected_id(&menu), 1);
+        assert_eq!(menu.handle(MenuAction::Up), None);
+        assert_eq!(selected_id(&menu), 5);
+        menu.handle(MenuAction::Down);
+        assert_eq!(selected_id(&menu), 1);
+        menu.handle(MenuAction::Down);
+        assert_eq!(selected_id(&menu), 2);
+    }
+
+    #[test]
+    fn test_widgets() {
+        let mut menu = Menu::new(options());
+        assert_eq!(
+            menu.handle(MenuAction::Right),
+            Some(MenuEvent::Slid(1, 0.6))
+        );
+        for _ in 0..10 {
+            menu.handle(MenuAction::Right);
+        }
+        assert_eq!(menu.current().items[1].widget, Widget::Slider(1.0));
+        assert_eq!(menu.handle(MenuAction::Right), None);
+
+        menu.current_mut().select(2);
+        assert_eq!(
+            menu.handle(MenuAction::Select),
+            Some(MenuEvent::Toggled(2, true))
+        );
+
+        menu.current_mut().select(3);
+        assert_eq!(menu.handle(MenuAction::Left), Some(MenuEvent::Chosen(3, 1)));
+        assert_eq!(
+            menu.current().items[3]
+                .value_text(&Strings::default())
+                .unwrap(),
+            "< Hard >"
+        );
+
+        menu.current_mut().select(5);
+        assert_eq!(menu.handle(MenuAction::Select), Some(MenuEvent::Pressed(5)));
+    }
+
+    #[test]
+    fn test_empty_list() {
+        let mut menu = Menu::new(Screen::new("Empty", vec![Item::list(0, "Mode", vec![], 0)]));
+        assert_eq!(menu.handle(MenuAction::Left), None);
+        assert_eq!(menu.handle(MenuAction::Right), None);
+        assert_eq!(
+            menu.current().items[0].value_text(&Strings::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_key_capture() {
+        let mut menu = Menu::new(options());
+        menu.current_mut().select(4);
+        assert_eq!(menu.capture_key("Space"), None);
+        menu.handle(MenuAction::Select);
+        assert!(menu.is_capturing());
+        assert_eq!(menu.handle(MenuAction::Down), None);
+        assert_eq!(
+            menu.capture_key("Space"),
+            Some(MenuEvent::KeyCaptured(4, "Space".to_string()))
+        );
+        assert!(!menu.is_capturing());
+        let strings = Strings::default();
+        assert_eq!(
+            menu.current().items[4].value_text(&strings).unwrap(),
+            "Space"
+        );
+
+        menu.handle(MenuAction::Select);
+        assert_eq!(
+            menu.current().items[4].value_text(&strings).unwrap(),
+            "Press a key"
+        );
+        menu.handle(MenuAction::Back);
+        assert!(!menu.is_capturing());
+        assert_eq!(
+            menu.current().items[4].value_text(&strings).unwrap(),
+            "Space"
+        );
+    }
+
+    #[test]
+    fn test_back() {
+        let mut menu = Menu::new(Screen::new("Main", vec![Item::button(10, "Options")]));
+        assert_eq!(menu.handle(MenuAction::Back), None);
+        menu.open(options());
+        assert_eq!(menu.current().title, "Options");
+        assert_eq!(menu.handle(MenuAction::Back), Some(MenuEvent::Closed));
+        assert_eq!(menu.current().title, "Main");
+        menu.open(options());
+        menu.open(options());
+        menu.back_to_root();
+        assert_eq!(menu.current().title, "Main");
+    }
+
+    #[test]
+    fn test_draw_highlights_selected() {
+        let assets = Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets");
+        let mut renderer = SoftwareRenderer::with_bundled_font((800, 600), &assets).unwrap();
+        renderer.clear();
+
+        let mut menu = Menu::new(options());
+        menu.current_mut().select(5);
+        menu.draw(&mut renderer, &Strings::default()).unwrap();
+
+        let image = renderer.image();
+        let count = |color: Color| {
+            image
+                .pixels()
+                .filter(|p| p.0[0..3] == [color.r, color.g, color.b])
+                .count()
+        };
+        assert!(count(SELECTED_COLOR) > 0);
+        assert!(count(ITEM_COLOR) > 0);
+        assert!(count(LABEL_COLOR) > 0);
+    }
+}
@@ -0,0 +1,136 @@
+use crate::widget::{Item, Widget, SLIDER_STEP};
+use crate::{MenuAction, MenuEvent};
+
+pub struct Screen<Id> {
+    pub title: String,
+    pub items: Vec<Item<Id>>,
+    selected: usize,
+}
+
+impl<Id: Copy> Screen<Id> {
+    pub fn new(title: &str, items: Vec<Item<Id>>) -> Self {
+        let selected = items.iter().position(Item::selectable).unwrap_or(0);
+        Screen {
+            title: title.to_string(),
+            items,
+            selected,
+        }
+    }
+
+    pub fn selected(&self) -> Option<&Item<Id>> {
+        self.items
+            .get(self.selected)
+            .filter(|item| item.selectable())
+    }
+
+    pub fn select(&mut self, id: Id)
+    where
+        Id: PartialEq,
+    {
+        if let Some(index) = self
+            .items
+            .iter()
+            .position(|item| item.id == id && item.selectable())
+        {
+            self.selected = index;
+        }
+    }
+
+    pub fn is_capturing(&self) -> bool {
+        self.items
+            .iter()
+            .any(|item| matches!(item.widget, Widget::KeyCapture { waiting: true, .. }))
+    }
+
+    pub(crate) fn handle(&mut self, action: MenuAction) -> Option<MenuEvent<Id>> {
+        match action {
+            MenuAction::Up => self.step(-1),
+            MenuAction::Down => self.step(1),
+            MenuAction::Left | MenuAction::Right | MenuAction::Select => {
+                return self.use_selected(action)
+            }
+            MenuAction::Back => {}
+        }
+        None
+    }
+
+    pub(crate) fn capture_key(&mut self, name: &str) -> Option<MenuEvent<Id>> {
+        let item = self.items.get_mut(self.selected)?;
+        match &mut item.widget {
+            Widget::KeyCapture { key, waiting } if *waiting => {
+                *key = name.to_string();
+                *waiting = false;
+                Some(MenuEvent::KeyCaptured(item.id, name.to_string()))
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn cancel_capture(&mut self) {
+        for item in &mut self.items {
+            if let Widget::KeyCapture { waiting, .. } = &mut item.widget {
+                *waiting = false;
+            }
+        }
+    }
+
+    pub(crate) fn selected_index(&self) -> usize {
+        self.selected
+    }
+
+    // Moves the selection to the next selectable item in the direction,
+    // wrapping around at the ends
+    fn step(&mut self, direction: isize) {
+        let count = self.items.len() as isize;
+        let mut index = self.selected as isize;
+        for _ in 0..count {
+            index = (index + direction).rem_euclid(count);
+            if self.items[index as usize].selectable() {
+                self.selected = index as usize;
+                return;
+            }
+        }
+    }
+
+    fn use_selected(&mut self, action: MenuAction) -> Option<MenuEvent<Id>> {
+        let item = self.items.get_mut(self.selected)?;
+        let id = item.id;
+        match (&mut item.widget, action) {
+            (Widget::Button, MenuAction::Select) => Some(MenuEvent::Pressed(id)),
+            // A list without options has nothing to choose
+            (Widget::List { options, selected }, _) if !options.is_empty() => {
+                *selected = if action == MenuAction::Left {
+                    (*selected + options.len() - 1) % options.len()
+                } else {
+                    (*selected + 1) % options.len()
+                };
+                Some(MenuEvent::Chosen(id, *selected))
+            }
+            (Widget::Slider(value), MenuAction::Left | MenuAction::Right) => {
+                let step = if action == MenuAction::Left {
+                    -SLIDER_STEP
+                } else {
+                    SLIDER_STEP
+                };
+                // Rounded to whole steps so repeated presses don't drift
+                let new_value = ((*value + step) / SLIDER_STEP).round() * SLIDER_STEP;
+                let new_value = new_value.clamp(0.0, 1.0);
+                if (new_value - *value).abs() < f32::EPSILON {
+                    None
+                } else {
+                    *value = new_value;
+                    Some(MenuEvent::Slid(id, new_value))
+                }
+            }
+            (Widget::Toggle(on), _) => {
+                *on = !*on;
+                Some(MenuEvent::Toggled(id, *on))
+            }
+            (Widget::KeyCapture { waiting, .. }, MenuAction::Select) => {
+                *waiting = true;
+                None
+            }
+            _ => None,
+        }
+    }
+}
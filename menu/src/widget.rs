@@ -0,0 +1,99 @@
+use localization::Strings;
+
+// How much a slider moves for each press of left or right
+pub const SLIDER_STEP: f32 = 0.1;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Widget {
+    // Does something when selected
+    Button,
+    // Only shows text and can't be selected
+    Label,
+    // One of several options, changed with left and right
+    List {
+        options: Vec<String>,
+        selected: usize,
+    },
+    // A value from 0 to 1
+    Slider(f32),
+    Toggle(bool),
+    // Shows the key used for something, and waits for a new key when
+    // selected
+    KeyCapture {
+        key: String,
+        waiting: bool,
+    },
+}
+
+// An entry on a menu screen. The id tells the game which entry an event
+// is about.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Item<Id> {
+    pub id: Id,
+    pub label: String,
+    pub widget: Widget,
+}
+
+impl<Id> Item<Id> {
+    pub fn button(id: Id, label: &str) -> Self {
+        Self::new(id, label, Widget::Button)
+    }
+
+    pub fn label(id: Id, text: &str) -> Self {
+        Self::new(id, text, Widget::Label)
+    }
+
+    pub fn list(id: Id, label: &str, options: Vec<String>, selected: usize) -> Self {
+        Self::new(id, label, Widget::List { options, selected })
+    }
+
+    pub fn slider(id: Id, label: &str, value: f32) -> Self {
+        Self::new(id, label, Widget::Slider(value.clamp(0.0, 1.0)))
+    }
+
+    pub fn toggle(id: Id, label: &str, on: bool) -> Self {
+        Self::new(id, label, Widget::Toggle(on))
+    }
+
+    pub fn key_capture(id: Id, label: &str, key: &str) -> Self {
+        Self::new(
+            id,
+            label,
+            Widget::KeyCapture {
+                key: key.to_string(),
+                waiting: false,
+            },
+        )
+    }
+
+    fn new(id: Id, label: &str, widget: Widget) -> Self {
+        Item {
+            id,
+            label: label.to_string(),
+            widget,
+        }
+    }
+
+    pub fn selectable(&self) -> bool {
+        self.widget != Widget::Label
+    }
+
+    // The text shown to the right of the label, for widgets that have a
+    // value
+    pub fn value_text(&self, strings: &Strings) -> Option<String> {
+        match &self.widget {
+            Widget::Button | Widget::Label | Widget::Slider(_) => None,
+            Widget::List { options, selected } => options
+                .get(*selected)
+                .map(|option| format!("< {} >", option)),
+            Widget::Toggle(on) => {
+                let id = if *on { "menu.on" } else { "menu.off" };
+                Some(strings.get(id).to_string())
+            }
+            Widget::KeyCapture { waiting: true, .. } => {
+                Some(strings.get("menu.press_a_key").to_string())
+            }
+            Widget::KeyCapture { key, .. } => Some(key.clone()),
+        }
+    }
+}
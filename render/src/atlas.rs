@@ -0,0 +1,135 @@
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
+pub struct FrameRect {
+    pub x: u32,
+    pub y: u32,
+    pub w: u32,
+    pub h: u32,
+}
+
+// The frames of a sprite sheet. The frames are read from a JSON file next
+// to the image, with the same name but the extension .json, like this:
+//
+// { "frames": [ { "x": 0, "y": 0, "w": 64, "h": 32 },
+//               { "x": 64, "y": 0, "w": 64, "h": 32 } ] }
+//
+// Images without such a file are a single frame covering the whole image.
+// An atlas always has at least one frame.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Atlas {
+    frames: Vec<FrameRect>,
+}
+
+impl Atlas {
+    pub fn single_frame(size: (u32, u32)) -> Self {
+        Atlas {
+            frames: vec![FrameRect {
+                x: 0,
+                y: 0,
+                w: size.0,
+                h: size.1,
+            }],
+        }
+    }
+
+    pub fn sidecar_path(image_path: &Path) -> PathBuf {
+        image_path.with_extension("json")
+    }
+
+    pub fn parse(json: &str) -> Result<Self, Box<dyn Error>> {
+        let atlas: Atlas = serde_json::from_str(json)?;
+        if atlas.frames.is_empty() {
+            return Err("A sprite sheet must have at least one frame".into());
+        }
+        Ok(atlas)
+    }
+
+    // Loads the frames for the image at image_path, which is of the given size
+    pub fn load(image_path: &Path, image_size: (u32, u32)) -> Result<Self, Box<dyn Error>> {
+        let sidecar = Atlas::sidecar_path(image_path);
+        if !sidecar.exists() {
+            return Ok(Atlas::single_frame(image_size));
+        }
+        let atlas = Atlas::parse(&std::fs::read_to_string(&sidecar)?)
+            .map_err(|e| format!("Failed to read {}: {}", sidecar.display(), e))?;
+        for frame in &atlas.frames {
+            let inside = |start: u32, length: u32, size: u32| {
+                start.checked_add(length).is_some_and(|end| end <= size)
+            };
+            if !inside(frame.x, frame.w, image_size.0) || !inside(frame.y, frame.h, image_size.1) {
+                return Err(
+                    format!("Frame {:?} is outside {}", frame, image_path.display()).into(),
+                );
+            }
+        }
+        Ok(atlas)
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    // Frames outside the sheet fall back to the last frame
+    pub fn frame(&self, index: usize) -> FrameRect {
+        self.frames[index.min(self.frames.len() - 1)]
+    }
+
+    pub fn frame_size(&self) -> (u32, u32) {
+        let first = self.frames[0];
+        (first.w, first.h)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let atlas = Atlas::parse(
+            r#"{ "frames": [ { "x": 0, "y": 0, "w": 64, "h": 32 },
+                             { "x": 64, "y": 0, "w": 64, "h": 32 } ] }"#,
+        )
+        .unwrap();
+        assert_eq!(atlas.frame_count(), 2);
+        assert_eq!(atlas.frame(1).x, 64);
+        assert_eq!(atlas.frame(7).x, 64);
+        assert_eq!(atlas.frame_size(), (64, 32));
+    }
+
+    #[test]
+    fn test_parse_rejects_empty_sheet() {
+        assert!(Atlas::parse(r#"{ "frames": [] }"#).is_err());
+        assert!(Atlas::parse(r#"{ "frame": [] }"#).is_err());
+    }
+
+    #[test]
+    fn test_frames_outside_the_image() {
+        let dir = tempfile::tempdir().unwrap();
+        let image = dir.path().join("sheet.png");
+        let sidecar = Atlas::sidecar_path(&image);
+        std::fs::write(
+            &sidecar,
+            r#"{ "frames": [ { "x": 4294967295, "y": 0, "w": 2, "h": 2 } ] }"#,
+        )
+        .unwrap();
+        assert!(Atlas::load(&image, (64, 32)).is_err());
+        std::fs::write(
+            &sidecar,
+            r#"{ "frames": [ { "x": 0, "y": 0, "w": 64, "h": 32 } ] }"#,
+        )
+        .unwrap();
+        assert!(Atlas::load(&image, (64, 32)).is_ok());
+        assert!(Atlas::load(&image, (63, 32)).is_err());
+    }
+
+    #[test]
+    fn test_image_without_sidecar_is_one_frame() {
+        let atlas = Atlas::load(Path::new("does_not_exist.png"), (10, 20)).unwrap();
+        assert_eq!(atlas, Atlas::single_frame((10, 20)));
+    }
+}
@@ -0,0 +1,177 @@
+use std::borrow::Cow;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::FontType;
+
+// Built into the executable, so there is always a font to draw text with
+pub const BUNDLED_FONT: &[u8] = include_bytes!("../../assets/fonts/DejaVuSans-Bold.ttf");
+
+// Where the common Linux distributions put the font the game was designed
+// with. These are tried after the configured fonts, before the bundled one.
+pub const SYSTEM_FONTS: &[&str] = &[
+    "/usr/share/fonts/TTF/OpenSans-ExtraBold.ttf",
+    "/usr/share/fonts/truetype/open-sans/OpenSans-ExtraBold.ttf",
+    "/usr/share/fonts/open-sans/OpenSans-ExtraBold.ttf",
+    "/usr/share/fonts/opentype/open-sans/OpenSans-ExtraBold.ttf",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FontSource {
+    File(PathBuf),
+    Bundled,
+}
+
+impl FontSource {
+    pub fn data(&self) -> Result<Cow<'static, [u8]>, Box<dyn Error>> {
+        match self {
+            FontSource::File(path) => {
+                let data = std::fs::read(path)
+                    .map_err(|e| format!("Failed to read font {}: {}", path.display(), e))?;
+                Ok(Cow::Owned(data))
+            }
+            FontSource::Bundled => Ok(Cow::Borrowed(BUNDLED_FONT)),
+        }
+    }
+}
+
+// The font files to try for one kind of text, in order, and the size to
+// draw it at
+#[derive(Debug, Clone, Deserialize)]
+pub struct FontChoice {
+    #[serde(default)]
+    pub files: Vec<PathBuf>,
+    pub size: u16,
+}
+
+impl FontChoice {
+    // The first of the configured files that exists, then the first of the
+    // system fonts that exists, and the bundled font if none of them do
+    pub fn locate(&self) -> FontSource {
+        self.files
+            .iter()
+            .map(PathBuf::as_path)
+            .chain(SYSTEM_FONTS.iter().map(Path::new))
+            .find(|path| path.is_file())
+            .map_or(FontSource::Bundled, |path| {
+                FontSource::File(path.to_path_buf())
+            })
+    }
+}
+
+// The font for each font type. Font types missing from the file use the
+// built in choice.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct FontConfig {
+    pub title: FontChoice,
+    pub info: FontChoice,
+    pub small: FontChoice,
+}
+
+impl FontConfig {
+    pub fn parse(json: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    // Relative font files are found from the directory of the file
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let json = std::fs::read_to_string(path)?;
+        let mut config = FontConfig::parse(&json)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        for choice in [&mut config.title, &mut config.info, &mut config.small] {
+            for file in choice.files.iter_mut() {
+                *file = dir.join(&file);
+            }
+        }
+        Ok(config)
+    }
+
+    pub fn choice(&self, font_type: &FontType) -> &FontChoice {
+        match font_type {
+            FontType::Title => &self.title,
+            FontType::Info => &self.info,
+            FontType::Small => &self.small,
+        }
+    }
+
+    // Puts the file first in the search order of every font type
+    pub fn prefer_file(&mut self, file: &Path) {
+        for choice in [&mut self.title, &mut self.info, &mut self.small] {
+            choice.files.insert(0, file.to_path_buf());
+        }
+    }
+}
+
+impl Default for FontConfig {
+    fn default() -> Self {
+        FontConfig {
+            title: FontChoice {
+                files: vec![],
+                size: 100,
+            },
+            info: FontChoice {
+                files: vec![],
+                size: 40,
+            },
+            small: FontChoice {
+                files: vec![],
+                size: 18,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_configured_file_is_found_first() {
+        let bundled =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets/fonts/DejaVuSans-Bold.ttf");
+        let choice = FontChoice {
+            files: vec![PathBuf::from("/no/such/font.ttf"), bundled.clone()],
+            size: 10,
+        };
+        assert_eq!(choice.locate(), FontSource::File(bundled));
+    }
+
+    #[test]
+    fn test_load_per_font_type() {
+        let assets = Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets");
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fonts.json");
+        std::fs::write(
+            &path,
+            r#"{ "title": { "files": ["title.ttf"], "size": 80 },
+                 "small": { "size": 12 } }"#,
+        )
+        .unwrap();
+        let config = FontConfig::load(&path).unwrap();
+        assert_eq!(config.title.files, vec![dir.path().join("title.ttf")]);
+        assert_eq!(config.title.size, 80);
+        assert_eq!(config.small.size, 12);
+        assert_eq!(config.info.size, FontConfig::default().info.size);
+        let bundled = FontConfig::load(&assets.join("fonts.json")).unwrap();
+        assert_eq!(bundled.title.size, FontConfig::default().title.size);
+    }
+
+    #[test]
+    fn test_bundled_font_is_the_fallback() {
+        let choice = FontChoice {
+            files: vec![PathBuf::from("/no/such/font.ttf")],
+            size: 10,
+        };
+        if SYSTEM_FONTS.iter().all(|f| !Path::new(f).exists()) {
+            assert_eq!(choice.locate(), FontSource::Bundled);
+        }
+        assert_eq!(
+            FontSource::Bundled.data().unwrap().len(),
+            BUNDLED_FONT.len()
+        );
+    }
+}
@@ -0,0 +1,15 @@
+mod atlas;
+mod fonts;
+mod renderer;
+mod screen;
+mod sprite_files;
+mod text_cache;
+
+pub use atlas::{Atlas, FrameRect};
+pub use fonts::{FontChoice, FontConfig, FontSource, BUNDLED_FONT, SYSTEM_FONTS};
+pub use renderer::{FontType, Renderer, SpriteOptions, TextPosition};
+pub use screen::Screen;
+pub use sprite_files::{sprite_asset_files, sprite_files_using, BlendMode, SPRITE_FILES};
+pub use text_cache::TextCache;
+
+pub use shared_types::Color;
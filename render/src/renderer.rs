@@ -0,0 +1,94 @@
+use std::error::Error;
+
+use ecs_components::{Rotation, Sprite};
+use geometry::{Position, Rect};
+use shared_types::{Color, EntitySizes};
+
+use crate::Screen;
+
+pub enum TextPosition {
+    TopLeft(u32, u32),
+    Center(u32, u32),
+    TopRight(u32, u32),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum FontType {
+    Title,
+    Info,
+    // For tools like the debug overlay
+    Small,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct SpriteOptions {
+    pub rotation: Option<Rotation>,
+    // Index into the frames of the sprite sheet
+    pub frame: usize,
+    // Size relative to the size of the frame, scaling around the top left
+    // corner. A rotation pivot is scaled along with the sprite.
+    pub scale: f32,
+    // Multiplied into the colors of the sprite. The alpha fades the sprite.
+    pub tint: Color,
+}
+
+impl Default for SpriteOptions {
+    fn default() -> Self {
+        SpriteOptions {
+            rotation: None,
+            frame: 0,
+            scale: 1.0,
+            tint: Color::WHITE,
+        }
+    }
+}
+
+// Everything the game draws goes through this trait, so the game can be
+// drawn to a window as well as to an image in memory
+pub trait Renderer {
+    // The logical screen, all drawing positions are relative to this
+    fn screen(&self) -> Screen;
+
+    fn clear(&mut self);
+
+    fn present(&mut self);
+
+    fn draw_sprite(
+        &mut self,
+        sprite: &Sprite,
+        topleft: Position,
+        options: &SpriteOptions,
+    ) -> Result<(), Box<dyn Error>>;
+
+    fn draw_circle(&mut self, x: f32, y: f32, radius: i16, color: Color);
+
+    fn fill_rect(&mut self, rect: Rect, color: Color);
+
+    fn draw_line(&mut self, from: Position, to: Position, color: Color);
+
+    fn draw_text(
+        &mut self,
+        text: &str,
+        text_position: TextPosition,
+        color: Color,
+        font_type: FontType,
+    ) -> Result<(), Box<dyn Error>>;
+
+    // The width and height of the text when drawn
+    fn text_size(&mut self, text: &str, font_type: FontType) -> Result<(u32, u32), Box<dyn Error>>;
+
+    // Loads the sprites drawn from the changed file again
+    fn reload(&mut self, file: &str) -> Result<(), Box<dyn Error>>;
+
+    // The size of a single frame of the sprite
+    fn sprite_size(&self, sprite: &Sprite) -> Result<(u32, u32), Box<dyn Error>>;
+
+    fn entity_sizes(&self) -> Result<EntitySizes, Box<dyn Error>> {
+        Ok(EntitySizes {
+            ufo_size: self.sprite_size(&Sprite::UFO)?,
+            player_size: self.sprite_size(&Sprite::Player)?,
+            basic_shot_size: self.sprite_size(&Sprite::BasicShot)?,
+            ufo_shot_size: self.sprite_size(&Sprite::UFOShot)?,
+        })
+    }
+}
@@ -0,0 +1,50 @@
+// The logical screen everything is drawn on. Its size stays the same when
+// the window is resized, the picture is scaled to fit the window instead.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Screen {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Screen {
+    pub fn new(size: (u32, u32)) -> Self {
+        Screen {
+            width: size.0,
+            height: size.1,
+        }
+    }
+
+    pub fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    // The point at the given fractions of the width and height, so (0.5,
+    // 0.5) is the center and (1.0, 0.0) is the top right corner
+    pub fn at(&self, x_fraction: f32, y_fraction: f32) -> (u32, u32) {
+        (
+            (self.width as f32 * x_fraction).round() as u32,
+            (self.height as f32 * y_fraction).round() as u32,
+        )
+    }
+
+    pub fn center(&self) -> (u32, u32) {
+        self.at(0.5, 0.5)
+    }
+
+    pub fn top_right(&self) -> (u32, u32) {
+        (self.width, 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_points() {
+        let screen = Screen::new((1200, 600));
+        assert_eq!(screen.center(), (600, 300));
+        assert_eq!(screen.top_right(), (1200, 0));
+        assert_eq!(screen.at(0.5, 5.0 / 6.0), (600, 500));
+    }
+}
@@ -0,0 +1,57 @@
+use std::path::Path;
+
+use ecs_components::Sprite;
+
+use crate::Atlas;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BlendMode {
+    Blend,
+    Add,
+}
+
+// The image file and blend mode used for drawing each sprite
+pub const SPRITE_FILES: &[(Sprite, &str, BlendMode)] = &[
+    (Sprite::UFO, "ufo.png", BlendMode::Blend),
+    (Sprite::Player, "player.png", BlendMode::Blend),
+    (Sprite::BasicShot, "basic_shot.png", BlendMode::Blend),
+    (Sprite::UFOShot, "ufo_shot.png", BlendMode::Blend),
+    (Sprite::Glow, "glow.png", BlendMode::Add),
+    (Sprite::Nebula, "nebula.png", BlendMode::Blend),
+];
+
+// The sprites drawn from the file, which is either an image or the sprite
+// sheet description next to it
+pub fn sprite_files_using(
+    file: &str,
+) -> impl Iterator<Item = &'static (Sprite, &'static str, BlendMode)> + '_ {
+    SPRITE_FILES.iter().filter(move |(_, image, _)| {
+        *image == file || Atlas::sidecar_path(Path::new(image)) == Path::new(file)
+    })
+}
+
+// Every file the sprites are made from, including sprite sheet
+// descriptions that may not exist
+pub fn sprite_asset_files() -> Vec<String> {
+    SPRITE_FILES
+        .iter()
+        .flat_map(|(_, image, _)| {
+            let sidecar = Atlas::sidecar_path(Path::new(image));
+            vec![image.to_string(), sidecar.to_string_lossy().into_owned()]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sprite_files_using() {
+        let using = |file| sprite_files_using(file).map(|s| s.0).collect::<Vec<_>>();
+        assert_eq!(using("ufo.png"), vec![Sprite::UFO]);
+        assert_eq!(using("ufo.json"), vec![Sprite::UFO]);
+        assert!(using("starfield.json").is_empty());
+        assert!(sprite_asset_files().contains(&"glow.json".to_string()));
+    }
+}
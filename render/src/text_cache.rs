@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+struct Entry<V> {
+    value: V,
+    last_used: u64,
+}
+
+// Keeps rendered text between frames, so text that doesn't change is only
+// rendered once. Text that hasn't been drawn for max_unused_frames frames
+// is dropped when a frame ends.
+pub struct TextCache<K, V> {
+    entries: HashMap<K, Entry<V>>,
+    frame: u64,
+    max_unused_frames: u64,
+}
+
+impl<K: Hash + Eq, V> TextCache<K, V> {
+    pub fn new(max_unused_frames: u64) -> Self {
+        TextCache {
+            entries: HashMap::new(),
+            frame: 0,
+            max_unused_frames,
+        }
+    }
+
+    // Returns the cached value for the key, rendering it with make if it
+    // isn't cached
+    pub fn get_or_try_insert_with<E>(
+        &mut self,
+        key: K,
+        make: impl FnOnce() -> Result<V, E>,
+    ) -> Result<&mut V, E> {
+        let frame = self.frame;
+        let entry = match self.entries.entry(key) {
+            std::collections::hash_map::Entry::Occupied(entry) => entry.into_mut(),
+            std::collections::hash_map::Entry::Vacant(entry) => entry.insert(Entry {
+                value: make()?,
+                last_used: frame,
+            }),
+        };
+        entry.last_used = frame;
+        Ok(&mut entry.value)
+    }
+
+    pub fn get_or_insert_with(&mut self, key: K, make: impl FnOnce() -> V) -> &mut V {
+        match self.get_or_try_insert_with(key, || Ok::<_, std::convert::Infallible>(make())) {
+            Ok(value) => value,
+            Err(never) => match never {},
+        }
+    }
+
+    pub fn end_frame(&mut self) {
+        let frame = self.frame;
+        let max_unused_frames = self.max_unused_frames;
+        self.entries
+            .retain(|_, entry| frame - entry.last_used < max_unused_frames);
+        self.frame += 1;
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(cache: &mut TextCache<String, usize>, text: &str, renders: &mut u32) -> usize {
+        *cache
+            .get_or_try_insert_with::<()>(text.to_string(), || {
+                *renders += 1;
+                Ok(text.len())
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_renders_once() {
+        let mut cache = TextCache::new(2);
+        let mut renders = 0;
+        for _ in 0..10 {
+            assert_eq!(render(&mut cache, "Score: 1", &mut renders), 8);
+            cache.end_frame();
+        }
+        assert_eq!(renders, 1);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_evicts_unused() {
+        let mut cache = TextCache::new(1);
+        let mut renders = 0;
+        render(&mut cache, "Score: 1", &mut renders);
+        cache.end_frame();
+        render(&mut cache, "Score: 2", &mut renders);
+        cache.end_frame();
+        assert_eq!(cache.len(), 1);
+        render(&mut cache, "Score: 2", &mut renders);
+        assert_eq!(renders, 2);
+        cache.end_frame();
+        cache.end_frame();
+        cache.end_frame();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_failed_render_is_not_cached() {
+        let mut cache: TextCache<String, usize> = TextCache::new(2);
+        assert!(cache
+            .get_or_try_insert_with("x".to_string(), || Err("failed"))
+            .is_err());
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,126 @@
+// Every finished game, kept in a file in the platform's local data
+// directory. Each line of the file is one game, so finishing a game only
+// appends to the file and a damaged line only loses that game.
+use std::error::Error;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use shared_types::{Difficulty, GameMode, RunStats};
+
+// The directory below the data directory and the file in it
+const APP_DIR_NAME: &str = "rideways";
+const HISTORY_FILE: &str = "history.jsonl";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunRecord {
+    // When the game ended, in seconds since 1970
+    pub ended_at: i64,
+    pub mode: GameMode,
+    pub difficulty: Difficulty,
+    pub score: u32,
+    pub stats: RunStats,
+}
+
+pub struct RunHistory {
+    path: PathBuf,
+}
+
+impl RunHistory {
+    pub fn new(path: &Path) -> Self {
+        RunHistory {
+            path: path.to_path_buf(),
+        }
+    }
+
+    // The history in the data directory, if the platform has one
+    pub fn find() -> Option<Self> {
+        dirs::data_local_dir()
+            .map(|dir| RunHistory::new(&dir.join(APP_DIR_NAME).join(HISTORY_FILE)))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn append(&self, record: &RunRecord) -> Result<(), Box<dyn Error>> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(record)?)?;
+        Ok(())
+    }
+
+    // The recorded games, oldest first. Lines that can't be read are
+    // skipped with a warning.
+    pub fn load(&self) -> Result<Vec<RunRecord>, Box<dyn Error>> {
+        if !self.path.exists() {
+            return Ok(vec![]);
+        }
+        let text = std::fs::read_to_string(&self.path)?;
+        let mut records = vec![];
+        for (index, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(line) {
+                Ok(record) => records.push(record),
+                Err(e) => eprintln!(
+                    "Skipping line {} of {}: {}",
+                    index + 1,
+                    self.path.display(),
+                    e
+                ),
+            }
+        }
+        Ok(records)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(score: u32) -> RunRecord {
+        let mut stats = RunStats {
+            shots_fired: 10,
+            hits: 4,
+            seconds_survived: 63.5,
+            deaths: 3,
+            ..RunStats::default()
+        };
+        stats.add_kill("ufo");
+        RunRecord {
+            ended_at: 1_700_000_000,
+            mode: GameMode::Hardcore,
+            difficulty: Difficulty::Easy,
+            score,
+            stats,
+        }
+    }
+
+    #[test]
+    fn test_append_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = RunHistory::new(&dir.path().join("history.jsonl"));
+        assert_eq!(history.load().unwrap(), vec![]);
+
+        history.append(&record(5)).unwrap();
+        history.append(&record(7)).unwrap();
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(history.path())
+            .unwrap();
+        writeln!(file, "{{ \"score\": ").unwrap();
+        history.append(&record(9)).unwrap();
+
+        let scores: Vec<_> = history.load().unwrap().iter().map(|r| r.score).collect();
+        assert_eq!(scores, vec![5, 7, 9]);
+        assert_eq!(history.load().unwrap()[0], record(5));
+    }
+}
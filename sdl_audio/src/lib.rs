@@ -0,0 +1,151 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use sdl2::mixer::{Channel, Chunk, Music, AUDIO_S16LSB, MAX_VOLUME};
+use sdl2::AudioSubsystem;
+
+use shared_types::{AudioBackend, MusicTrack, SoundEffect, Volumes, MUSIC_FILES, SOUND_FILES};
+
+// Effects playing at the same time. When all are busy, new effects are
+// skipped.
+const CHANNELS: i32 = 16;
+
+fn mixer_volume(level: f32) -> i32 {
+    (level * MAX_VOLUME as f32).round() as i32
+}
+
+pub struct Audio {
+    _audio: AudioSubsystem,
+    sound_dir: PathBuf,
+    effects: BTreeMap<SoundEffect, Chunk>,
+    music: BTreeMap<MusicTrack, Music<'static>>,
+    playing: Option<MusicTrack>,
+    // The channels each effect was last played on
+    voices: BTreeMap<SoundEffect, Vec<Channel>>,
+    volumes: Volumes,
+}
+
+impl Audio {
+    // Opens the audio device and loads all sounds and music from sound_dir
+    pub fn new(sdl: &sdl2::Sdl, sound_dir: &Path) -> Result<Self, Box<dyn Error>> {
+        let audio_subsystem = sdl.audio()?;
+        sdl2::mixer::open_audio(44100, AUDIO_S16LSB, 2, 1024)?;
+        sdl2::mixer::allocate_channels(CHANNELS);
+        let mut audio = Audio {
+            _audio: audio_subsystem,
+            sound_dir: sound_dir.to_path_buf(),
+            effects: BTreeMap::new(),
+            music: BTreeMap::new(),
+            playing: None,
+            voices: BTreeMap::new(),
+            volumes: Volumes::default(),
+        };
+        for (effect, file) in SOUND_FILES {
+            audio.load_effect(*effect, file)?;
+        }
+        for (track, file) in MUSIC_FILES {
+            audio.load_music(*track, file)?;
+        }
+        audio.set_volumes(Volumes::default());
+        Ok(audio)
+    }
+
+    fn load_effect(&mut self, effect: SoundEffect, file: &str) -> Result<(), Box<dyn Error>> {
+        let path = self.sound_dir.join(file);
+        let chunk = Chunk::from_file(&path)
+            .map_err(|e| format!("Failed to load {}: {}", path.display(), e))?;
+        self.effects.insert(effect, chunk);
+        Ok(())
+    }
+
+    fn load_music(&mut self, track: MusicTrack, file: &str) -> Result<(), Box<dyn Error>> {
+        let path = self.sound_dir.join(file);
+        let music = Music::from_file(&path)
+            .map_err(|e| format!("Failed to load {}: {}", path.display(), e))?;
+        if self.playing == Some(track) {
+            Music::halt();
+            self.playing = None;
+        }
+        self.music.insert(track, music);
+        Ok(())
+    }
+
+    pub fn volumes(&self) -> Volumes {
+        self.volumes
+    }
+}
+
+impl AudioBackend for Audio {
+    fn play(&mut self, id: SoundEffect, pan: f32) -> Result<(), Box<dyn Error>> {
+        let chunk = self
+            .effects
+            .get(&id)
+            .ok_or_else(|| format!("Missing sound {:?}", id))?;
+        // Failing to find a free channel just means the sound isn't heard
+        if let Ok(channel) = Channel::all().play(chunk, 0) {
+            // Equal power panning, so sounds in the middle aren't louder
+            let angle = (pan.clamp(-1.0, 1.0) + 1.0) * std::f32::consts::FRAC_PI_4;
+            let level = |gain: f32| (gain * 255.0).round() as u8;
+            channel.set_panning(level(angle.cos()), level(angle.sin()))?;
+            let voices = self.voices.entry(id).or_default();
+            voices.retain(|c| c.is_playing() && *c != channel);
+            voices.push(channel);
+            for (other, voices) in self.voices.iter_mut() {
+                if *other != id {
+                    voices.retain(|c| *c != channel);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn voices_playing(&self, id: SoundEffect) -> usize {
+        self.voices
+            .get(&id)
+            .map_or(0, |voices| voices.iter().filter(|c| c.is_playing()).count())
+    }
+
+    fn play_music(&mut self, track: MusicTrack) -> Result<(), Box<dyn Error>> {
+        if self.playing == Some(track) {
+            return Ok(());
+        }
+        // Recorded before playing, so a track that fails is only tried, and
+        // reported, once. Reloading the file tries it again. When it fails
+        // the old track is stopped, so it isn't heard in place of the new one.
+        self.playing = Some(track);
+        let result = match self.music.get(&track) {
+            Some(music) => music.play(-1).map_err(|e| e.into()),
+            None => Err(format!("Missing music {:?}", track).into()),
+        };
+        if result.is_err() {
+            Music::halt();
+        }
+        result
+    }
+
+    fn set_volumes(&mut self, volumes: Volumes) {
+        self.volumes = volumes;
+        Music::set_volume(mixer_volume(volumes.music_level()));
+        Channel::all().set_volume(mixer_volume(volumes.effects_level()));
+    }
+
+    fn reload(&mut self, file: &str) -> Result<(), Box<dyn Error>> {
+        for (effect, _) in SOUND_FILES.iter().filter(|(_, f)| *f == file) {
+            self.load_effect(*effect, file)?;
+        }
+        for (track, _) in MUSIC_FILES.iter().filter(|(_, f)| *f == file) {
+            self.load_music(*track, file)?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Audio {
+    fn drop(&mut self) {
+        Music::halt();
+        self.music.clear();
+        self.effects.clear();
+        sdl2::mixer::close_audio();
+    }
+}
@@ -1,24 +1,25 @@
 use std::collections::btree_map::BTreeMap;
 use std::error::Error;
+use std::path::{Path, PathBuf};
 
 use sdl2::gfx::primitives::DrawRenderer;
 use sdl2::image::LoadTexture;
-use sdl2::pixels::Color;
+use sdl2::pixels;
 use sdl2::render::{BlendMode, Texture, TextureCreator};
+use sdl2::rwops::RWops;
 use sdl2::ttf::Font;
-use sdl2::video::WindowContext;
+use sdl2::video::{FullscreenType, WindowContext};
 use sdl2::EventPump;
 
 use ecs_components::Sprite;
-use shared_types::EntitySizes;
+use geometry::{Position, Rect};
+use render::{
+    sprite_files_using, Atlas, Color, FontChoice, FontConfig, FontSource, FontType, Renderer,
+    Screen, SpriteOptions, TextCache, TextPosition, BUNDLED_FONT, SPRITE_FILES,
+};
 
 type Canvas = sdl2::render::Canvas<sdl2::video::Window>;
-type Map<'a> = BTreeMap<Sprite, (Texture<'a>, (u32, u32))>;
-
-pub enum TextPosition {
-    Center(u32, u32),
-    TopRight(u32, u32),
-}
+type Map<'a> = BTreeMap<Sprite, (Texture<'a>, Atlas)>;
 
 pub struct Window {
     pub canvas: Canvas,
@@ -30,25 +31,58 @@ pub struct Contexts {
     ttf: sdl2::ttf::Sdl2TtfContext,
 }
 
-pub enum FontType {
-    Title,
-    Info,
-}
-
 impl Contexts {
     pub fn new() -> Result<Self, Box<dyn Error>> {
         let sdl = sdl2::init()?;
         let ttf = sdl2::ttf::init()?;
         Ok(Self { sdl, ttf })
     }
+
+    pub fn sdl(&self) -> &sdl2::Sdl {
+        &self.sdl
+    }
+}
+
+fn sdl_color(color: Color) -> pixels::Color {
+    pixels::Color::RGBA(color.r, color.g, color.b, color.a)
+}
+
+// How text is turned into pixels. Solid is fast but has jagged edges,
+// blended is smooth.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum TextMode {
+    Solid,
+    Blended,
+}
+
+// Rendered text is kept for this many frames after it was last drawn
+const TEXT_CACHE_FRAMES: u64 = 60;
+
+fn load_font<'a>(
+    contexts: &'a Contexts,
+    choice: &FontChoice,
+) -> Result<Font<'a, 'a>, Box<dyn Error>> {
+    let font = match choice.locate() {
+        FontSource::File(path) => contexts.ttf.load_font(path, choice.size)?,
+        FontSource::Bundled => contexts
+            .ttf
+            .load_font_from_rwops(RWops::from_bytes(BUNDLED_FONT)?, choice.size)?,
+    };
+    Ok(font)
 }
 
 pub struct Graphics<'a> {
     pub event_pump: EventPump,
-    renderer: Renderer<'a>,
+    renderer: SpriteRenderer<'a>,
     texture_creator: &'a TextureCreator<WindowContext>,
     title_font: Font<'a, 'a>,
     info_font: Font<'a, 'a>,
+    small_font: Font<'a, 'a>,
+    text_mode: TextMode,
+    // Text is rendered in white and colored when drawn, so fading text
+    // doesn't need rendering again
+    text_cache: TextCache<(String, FontType, TextMode), Texture<'a>>,
+    gfx_used: bool,
 }
 
 impl<'a> Graphics<'a> {
@@ -56,62 +90,83 @@ impl<'a> Graphics<'a> {
         window: Window,
         contexts: &'a Contexts,
         texture_creator: &'a TextureCreator<WindowContext>,
+        asset_dir: &Path,
+        fonts: &FontConfig,
     ) -> Result<Graphics<'a>, Box<dyn Error>> {
-        let renderer = Renderer::new(window.canvas, texture_creator)?;
+        let renderer = SpriteRenderer::new(window.canvas, texture_creator, asset_dir)?;
 
-        // TODO: Find a way to locate font files
-        let title_font = contexts
-            .ttf
-            .load_font("/usr/share/fonts/TTF/OpenSans-ExtraBold.ttf", 100)?;
-        let info_font = contexts
-            .ttf
-            .load_font("/usr/share/fonts/TTF/OpenSans-ExtraBold.ttf", 40)?;
+        let title_font = load_font(contexts, &fonts.title)?;
+        let info_font = load_font(contexts, &fonts.info)?;
+        let small_font = load_font(contexts, &fonts.small)?;
         Ok(Graphics {
             event_pump: window.event_pump,
             renderer,
             texture_creator,
             title_font,
             info_font,
+            small_font,
+            text_mode: TextMode::Solid,
+            text_cache: TextCache::new(TEXT_CACHE_FRAMES),
+            gfx_used: false,
         })
     }
 
+    pub fn set_text_mode(&mut self, mode: TextMode) {
+        self.text_mode = mode;
+    }
+
+    // Makes a resizable window drawing a screen of logical_size. When the
+    // window gets another size, the drawing is scaled to fit the window
+    // while keeping its aspect ratio, leaving black bars at the sides.
     pub fn make_window(
         contexts: &Contexts,
         name: &str,
-        size: (u32, u32),
+        logical_size: (u32, u32),
+        window_size: (u32, u32),
+        vsync: bool,
     ) -> Result<Window, Box<dyn Error>> {
         let video_context = contexts.sdl.video()?;
-        let canvas = video_context
-            .window(name, size.0, size.1)
+        let window = video_context
+            .window(name, window_size.0, window_size.1)
             .position_centered()
-            .build()?
-            .into_canvas()
-            .present_vsync()
+            .resizable()
             .build()?;
+        let mut canvas = if vsync {
+            window.into_canvas().present_vsync().build()?
+        } else {
+            window.into_canvas().build()?
+        };
+        canvas.set_logical_size(logical_size.0, logical_size.1)?;
         let event_pump = contexts.sdl.event_pump()?;
         Ok(Window { event_pump, canvas })
     }
 
-    pub fn entity_sizes(&self) -> Result<EntitySizes, Box<dyn Error>> {
-        self.renderer.entity_sizes()
+    pub fn is_fullscreen(&self) -> bool {
+        self.renderer.canvas.window().fullscreen_state() != FullscreenType::Off
     }
 
-    pub fn clear(&mut self) {
-        self.renderer.canvas.set_draw_color(Color::RGB(0, 0, 0));
-        self.renderer.canvas.clear();
+    pub fn set_fullscreen(&mut self, fullscreen: bool) -> Result<(), Box<dyn Error>> {
+        let state = if fullscreen {
+            FullscreenType::Desktop
+        } else {
+            FullscreenType::Off
+        };
+        self.renderer.canvas.window_mut().set_fullscreen(state)?;
+        Ok(())
     }
 
-    pub fn present(&mut self) {
-        self.renderer.present();
+    // The size of the window when it isn't fullscreen
+    pub fn window_size(&self) -> (u32, u32) {
+        self.renderer.canvas.window().size()
     }
 
-    pub fn draw_circle(&mut self, x: f32, y: f32, radius: i16) {
-        let _ = self.renderer.canvas.filled_circle(
-            x as i16,
-            y as i16,
-            radius,
-            Color::RGB(150, 200, 200),
-        );
+    pub fn set_window_size(&mut self, size: (u32, u32)) -> Result<(), Box<dyn Error>> {
+        self.renderer.canvas.window_mut().set_size(size.0, size.1)?;
+        Ok(())
+    }
+
+    pub fn toggle_fullscreen(&mut self) -> Result<(), Box<dyn Error>> {
+        self.set_fullscreen(!self.is_fullscreen())
     }
 
     // This function is a hack to avoid what I believe to be a bug in SDL2. When
@@ -119,89 +174,155 @@ impl<'a> Graphics<'a> {
     // a small artifact from the last color used with the SDL2_gfx functions sometimes
     // appear on the lower right corner of the first texture being copied. By drawing
     // a small black circle, I make this artifact black and thus invisble in this game.
-    pub fn back_to_black(&mut self) {
+    // It is called automatically before copying a texture after SDL2_gfx has been used.
+    fn back_to_black(&mut self) {
+        if self.gfx_used {
+            let _ = self
+                .renderer
+                .canvas
+                .filled_circle(2, 2, 2, pixels::Color::RGB(0, 0, 0));
+            self.gfx_used = false;
+        }
+    }
+}
+
+impl<'a> Renderer for Graphics<'a> {
+    fn screen(&self) -> Screen {
+        Screen::new(self.renderer.canvas.logical_size())
+    }
+
+    fn clear(&mut self) {
+        self.renderer
+            .canvas
+            .set_draw_color(pixels::Color::RGB(0, 0, 0));
+        self.renderer.canvas.clear();
+    }
+
+    fn present(&mut self) {
+        self.renderer.present();
+        self.text_cache.end_frame();
+    }
+
+    fn draw_circle(&mut self, x: f32, y: f32, radius: i16, color: Color) {
         let _ = self
             .renderer
             .canvas
-            .filled_circle(2, 2, 2, Color::RGB(0, 0, 0));
+            .filled_circle(x as i16, y as i16, radius, sdl_color(color));
+        self.gfx_used = true;
     }
 
-    pub fn draw_sprite(
+    fn draw_sprite(
         &mut self,
-        target: &ecs_components::Position,
         sprite: &Sprite,
+        topleft: Position,
+        options: &SpriteOptions,
     ) -> Result<(), Box<dyn Error>> {
-        self.renderer.render(target, sprite)
+        self.back_to_black();
+        self.renderer.render(sprite, topleft, options)
+    }
+
+    fn fill_rect(&mut self, rect: Rect, color: Color) {
+        let canvas = &mut self.renderer.canvas;
+        canvas.set_blend_mode(BlendMode::Blend);
+        canvas.set_draw_color(sdl_color(color));
+        let _ = canvas.fill_rect(sdl2::rect::Rect::new(
+            rect.left().round() as i32,
+            rect.top().round() as i32,
+            rect.width().round() as u32,
+            rect.height().round() as u32,
+        ));
+    }
+
+    fn draw_line(&mut self, from: Position, to: Position, color: Color) {
+        let canvas = &mut self.renderer.canvas;
+        canvas.set_blend_mode(BlendMode::Blend);
+        canvas.set_draw_color(sdl_color(color));
+        let _ = canvas.draw_line(
+            sdl2::rect::Point::new(from.0.round() as i32, from.1.round() as i32),
+            sdl2::rect::Point::new(to.0.round() as i32, to.1.round() as i32),
+        );
     }
 
-    pub fn draw_text(
+    fn draw_text(
         &mut self,
         text: &str,
         text_position: TextPosition,
         color: Color,
         font_type: FontType,
     ) -> Result<(), Box<dyn Error>> {
+        self.back_to_black();
         let font = match font_type {
             FontType::Info => &self.info_font,
+            FontType::Small => &self.small_font,
             FontType::Title => &self.title_font,
         };
-        let texture = self
-            .texture_creator
-            .create_texture_from_surface(font.render(text).solid(color)?)?;
+        let texture_creator = self.texture_creator;
+        let mode = self.text_mode;
+        let key = (text.to_string(), font_type, mode);
+        let texture = self.text_cache.get_or_try_insert_with(key, || {
+            let rendered = font.render(text);
+            let white = sdl_color(Color::WHITE);
+            let surface = match mode {
+                TextMode::Solid => rendered.solid(white)?,
+                TextMode::Blended => rendered.blended(white)?,
+            };
+            Ok::<_, Box<dyn Error>>(texture_creator.create_texture_from_surface(surface)?)
+        })?;
+        texture.set_color_mod(color.r, color.g, color.b);
+        texture.set_alpha_mod(color.a);
         let query = texture.query();
+        let (width, height) = (query.width as i32, query.height as i32);
         let top_left = match text_position {
-            TextPosition::Center(x, y) => (x - query.width / 2, y - query.height / 2),
-            TextPosition::TopRight(x, y) => (x - query.width, y),
+            TextPosition::Center(x, y) => (x as i32 - width / 2, y as i32 - height / 2),
+            TextPosition::TopLeft(x, y) => (x as i32, y as i32),
+            TextPosition::TopRight(x, y) => (x as i32 - width, y as i32),
         };
-        let rect = sdl2::rect::Rect::new(
-            top_left.0 as i32,
-            top_left.1 as i32,
-            query.width,
-            query.height,
-        );
-        self.renderer.canvas.copy(&texture, None, rect)?;
+        let rect = sdl2::rect::Rect::new(top_left.0, top_left.1, query.width, query.height);
+        self.renderer.canvas.copy(texture, None, rect)?;
         Ok(())
     }
+
+    fn text_size(&mut self, text: &str, font_type: FontType) -> Result<(u32, u32), Box<dyn Error>> {
+        let font = match font_type {
+            FontType::Info => &self.info_font,
+            FontType::Small => &self.small_font,
+            FontType::Title => &self.title_font,
+        };
+        Ok(font.size_of(text)?)
+    }
+
+    fn reload(&mut self, file: &str) -> Result<(), Box<dyn Error>> {
+        self.renderer.reload(file)
+    }
+
+    fn sprite_size(&self, sprite: &Sprite) -> Result<(u32, u32), Box<dyn Error>> {
+        Ok(self.renderer.get_texture_size(*sprite)?)
+    }
 }
 
-pub struct Renderer<'a> {
+pub struct SpriteRenderer<'a> {
     map: Map<'a>,
     pub canvas: Canvas,
+    texture_creator: &'a TextureCreator<WindowContext>,
+    asset_dir: PathBuf,
 }
 
-impl<'a> Renderer<'a> {
+impl<'a> SpriteRenderer<'a> {
     pub fn new(
         canvas: Canvas,
         texture_creator: &'a TextureCreator<WindowContext>,
-    ) -> Result<Renderer<'a>, Box<dyn Error>> {
+        asset_dir: &Path,
+    ) -> Result<SpriteRenderer<'a>, Box<dyn Error>> {
         let map = Map::new();
-        let mut renderer = Renderer { map, canvas };
-        renderer.load_texture(Sprite::UFO, "ufo.png", texture_creator, BlendMode::Blend)?;
-        renderer.load_texture(
-            Sprite::Player,
-            "player.png",
-            texture_creator,
-            BlendMode::Blend,
-        )?;
-        renderer.load_texture(
-            Sprite::PlayerGhost,
-            "player_ghost.png",
+        let mut renderer = SpriteRenderer {
+            map,
+            canvas,
             texture_creator,
-            BlendMode::Blend,
-        )?;
-        renderer.load_texture(
-            Sprite::BasicShot,
-            "basic_shot.png",
-            texture_creator,
-            BlendMode::Blend,
-        )?;
-        renderer.load_texture(
-            Sprite::UFOShot,
-            "ufo_shot.png",
-            texture_creator,
-            BlendMode::Blend,
-        )?;
-        renderer.load_texture(Sprite::Glow, "glow.png", texture_creator, BlendMode::Add)?;
+            asset_dir: asset_dir.to_path_buf(),
+        };
+        for (sprite, filename, blend_mode) in SPRITE_FILES {
+            renderer.load_texture(*sprite, filename, *blend_mode)?;
+        }
         Ok(renderer)
     }
 
@@ -209,35 +330,74 @@ impl<'a> Renderer<'a> {
         &mut self,
         sprite: Sprite,
         filename: &str,
-        texture_creator: &'a TextureCreator<WindowContext>,
-        blend_mode: BlendMode,
+        blend_mode: render::BlendMode,
     ) -> Result<(), Box<dyn Error>> {
-        let mut texture = texture_creator.load_texture(filename)?;
+        let blend_mode = match blend_mode {
+            render::BlendMode::Blend => BlendMode::Blend,
+            render::BlendMode::Add => BlendMode::Add,
+        };
+        let path = self.asset_dir.join(filename);
+        let mut texture = self
+            .texture_creator
+            .load_texture(&path)
+            .map_err(|e| format!("Failed to load {}: {}", path.display(), e))?;
         texture.set_blend_mode(blend_mode);
         let query = texture.query();
-        let size = (query.width, query.height);
-        self.map.insert(sprite, (texture, size));
+        let atlas = Atlas::load(&path, (query.width, query.height))?;
+        self.map.insert(sprite, (texture, atlas));
+        Ok(())
+    }
+
+    // Loads the sprites drawn from the file again
+    pub fn reload(&mut self, file: &str) -> Result<(), Box<dyn Error>> {
+        for (sprite, filename, blend_mode) in sprite_files_using(file) {
+            self.load_texture(*sprite, filename, *blend_mode)?;
+        }
         Ok(())
     }
 
     pub fn render(
         &mut self,
-        position: &ecs_components::Position,
-        sprite: &ecs_components::Sprite,
+        sprite: &Sprite,
+        topleft: Position,
+        options: &SpriteOptions,
     ) -> Result<(), Box<dyn Error>> {
         let render_info = self
             .map
-            .get(sprite)
+            .get_mut(sprite)
             .ok_or_else(|| format!("Failed to get render info for {:?}", sprite))?;
-        let texture = &render_info.0;
-        let size = render_info.1;
+        let texture = &mut render_info.0;
+        let tint = options.tint;
+        texture.set_color_mod(tint.r, tint.g, tint.b);
+        texture.set_alpha_mod(tint.a);
+        let frame = render_info.1.frame(options.frame);
+        let source_rect = sdl2::rect::Rect::new(frame.x as i32, frame.y as i32, frame.w, frame.h);
         let dest_rect = sdl2::rect::Rect::new(
-            position.rect.left() as i32,
-            position.rect.top() as i32,
-            size.0,
-            size.1,
+            topleft.0 as i32,
+            topleft.1 as i32,
+            (frame.w as f32 * options.scale).round() as u32,
+            (frame.h as f32 * options.scale).round() as u32,
         );
-        self.canvas.copy(texture, None, dest_rect)?;
+        match options.rotation {
+            Some(rotation) => {
+                let pivot = rotation.pivot.map(|p| {
+                    sdl2::rect::Point::new(
+                        (p.0 * options.scale) as i32,
+                        (p.1 * options.scale) as i32,
+                    )
+                });
+                self.canvas.copy_ex(
+                    texture,
+                    source_rect,
+                    dest_rect,
+                    rotation.degrees as f64,
+                    pivot,
+                    false,
+                    false,
+                )?;
+            }
+            None => self.canvas.copy(texture, source_rect, dest_rect)?,
+        }
         Ok(())
     }
 
@@ -245,24 +405,11 @@ impl<'a> Renderer<'a> {
         self.canvas.present();
     }
 
-    pub fn entity_sizes(&self) -> Result<EntitySizes, Box<dyn Error>> {
-        let ufo_size = self.get_texture_size(Sprite::UFO)?;
-        let player_size = self.get_texture_size(Sprite::Player)?;
-        let basic_shot_size = self.get_texture_size(Sprite::BasicShot)?;
-        let ufo_shot_size = self.get_texture_size(Sprite::UFOShot)?;
-        Ok(EntitySizes {
-            ufo_size,
-            player_size,
-            basic_shot_size,
-            ufo_shot_size,
-        })
-    }
-
     fn get_texture_size(&self, sprite: Sprite) -> Result<(u32, u32), String> {
         let render_info = &self
             .map
             .get(&sprite)
             .ok_or(format!("Missing render info for {:?}", sprite))?;
-        Ok(render_info.1)
+        Ok(render_info.1.frame_size())
     }
 }
@@ -1,3 +1,22 @@
+use sdl2::controller::{Axis, Button};
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+
+use crate::{Control, KeyBindings};
+
+// How far a stick must be pushed before it counts as a direction
+const STICK_DEAD_ZONE: i16 = 12000;
+
+#[derive(Debug, Copy, Clone, Default)]
+struct Directions {
+    left: bool,
+    right: bool,
+    up: bool,
+    down: bool,
+}
+
+// The directions are held when a key, the D-pad or the stick holds them,
+// and fire when the key or the A button does
 #[derive(Debug, Copy, Clone, Default)]
 pub struct ControlState {
     pub left: bool,
@@ -5,6 +24,12 @@ pub struct ControlState {
     pub up: bool,
     pub down: bool,
     pub fire: bool,
+    pub back: bool,
+    keys: Directions,
+    dpad: Directions,
+    stick: Directions,
+    fire_key: bool,
+    fire_button: bool,
 }
 
 impl ControlState {
@@ -15,32 +40,152 @@ impl ControlState {
             up: false,
             down: false,
             fire: false,
+            back: false,
+            keys: Directions::default(),
+            dpad: Directions::default(),
+            stick: Directions::default(),
+            fire_key: false,
+            fire_button: false,
         }
     }
 
-    pub fn update(&mut self, event: &sdl2::event::Event) {
+    pub fn update(&mut self, event: &Event, bindings: &KeyBindings) {
         match event {
-            sdl2::event::Event::KeyUp {
+            Event::KeyUp {
                 keycode: Some(key), ..
-            } => match key {
-                sdl2::keyboard::Keycode::W => self.up = false,
-                sdl2::keyboard::Keycode::A => self.left = false,
-                sdl2::keyboard::Keycode::S => self.down = false,
-                sdl2::keyboard::Keycode::D => self.right = false,
-                sdl2::keyboard::Keycode::Return => self.fire = false,
-                _ => {}
-            },
-            sdl2::event::Event::KeyDown {
+            } => self.set_key(*key, bindings, false),
+            Event::KeyDown {
                 keycode: Some(key), ..
-            } => match key {
-                sdl2::keyboard::Keycode::W => self.up = true,
-                sdl2::keyboard::Keycode::A => self.left = true,
-                sdl2::keyboard::Keycode::S => self.down = true,
-                sdl2::keyboard::Keycode::D => self.right = true,
-                sdl2::keyboard::Keycode::Return => self.fire = true,
+            } => self.set_key(*key, bindings, true),
+            Event::ControllerButtonUp { button, .. } => self.set_button(*button, false),
+            Event::ControllerButtonDown { button, .. } => self.set_button(*button, true),
+            Event::ControllerAxisMotion { axis, value, .. } => match axis {
+                Axis::LeftX => {
+                    self.stick.left = *value < -STICK_DEAD_ZONE;
+                    self.stick.right = *value > STICK_DEAD_ZONE;
+                }
+                Axis::LeftY => {
+                    self.stick.up = *value < -STICK_DEAD_ZONE;
+                    self.stick.down = *value > STICK_DEAD_ZONE;
+                }
                 _ => {}
             },
             _ => {}
         }
+        self.left = self.keys.left || self.dpad.left || self.stick.left;
+        self.right = self.keys.right || self.dpad.right || self.stick.right;
+        self.up = self.keys.up || self.dpad.up || self.stick.up;
+        self.down = self.keys.down || self.dpad.down || self.stick.down;
+        self.fire = self.fire_key || self.fire_button;
+    }
+
+    fn set_key(&mut self, key: Keycode, bindings: &KeyBindings, pressed: bool) {
+        if key == Keycode::Escape {
+            self.back = pressed;
+        } else if let Some(control) = bindings.control(key) {
+            match control {
+                Control::Up => self.keys.up = pressed,
+                Control::Down => self.keys.down = pressed,
+                Control::Left => self.keys.left = pressed,
+                Control::Right => self.keys.right = pressed,
+                Control::Fire => self.fire_key = pressed,
+            }
+        }
+    }
+
+    fn set_button(&mut self, button: Button, pressed: bool) {
+        match button {
+            Button::DPadUp => self.dpad.up = pressed,
+            Button::DPadDown => self.dpad.down = pressed,
+            Button::DPadLeft => self.dpad.left = pressed,
+            Button::DPadRight => self.dpad.right = pressed,
+            Button::A => self.fire_button = pressed,
+            Button::B | Button::Back => self.back = pressed,
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stick(axis: Axis, value: i16) -> Event {
+        Event::ControllerAxisMotion {
+            timestamp: 0,
+            which: 0,
+            axis,
+            value,
+        }
+    }
+
+    fn key(key: Keycode, pressed: bool) -> Event {
+        let keymod = sdl2::keyboard::Mod::NOMOD;
+        if pressed {
+            Event::KeyDown {
+                timestamp: 0,
+                window_id: 0,
+                keycode: Some(key),
+                scancode: None,
+                keymod,
+                repeat: false,
+            }
+        } else {
+            Event::KeyUp {
+                timestamp: 0,
+                window_id: 0,
+                keycode: Some(key),
+                scancode: None,
+                keymod,
+                repeat: false,
+            }
+        }
+    }
+
+    fn button(button: Button, pressed: bool) -> Event {
+        if pressed {
+            Event::ControllerButtonDown {
+                timestamp: 0,
+                which: 0,
+                button,
+            }
+        } else {
+            Event::ControllerButtonUp {
+                timestamp: 0,
+                which: 0,
+                button,
+            }
+        }
+    }
+
+    #[test]
+    fn test_stick_and_keys_hold_directions_together() {
+        let bindings = KeyBindings::default();
+        let mut state = ControlState::new();
+        state.update(&stick(Axis::LeftX, -20000), &bindings);
+        state.update(&key(bindings.key(Control::Left), true), &bindings);
+        state.update(&stick(Axis::LeftX, 0), &bindings);
+        assert!(state.left);
+        state.update(&stick(Axis::LeftY, 20000), &bindings);
+        assert!(state.down);
+        state.update(&button(Button::DPadDown, false), &bindings);
+        assert!(state.down);
+        assert!(!state.up && !state.right);
+    }
+
+    #[test]
+    fn test_keys_and_dpad_hold_directions_and_fire_together() {
+        let bindings = KeyBindings::default();
+        let mut state = ControlState::new();
+        state.update(&button(Button::DPadLeft, true), &bindings);
+        state.update(&button(Button::A, true), &bindings);
+        for control in [Control::Left, Control::Fire] {
+            state.update(&key(bindings.key(control), true), &bindings);
+            state.update(&key(bindings.key(control), false), &bindings);
+        }
+        assert!(state.left && state.fire);
+        state.update(&button(Button::DPadLeft, false), &bindings);
+        state.update(&button(Button::A, false), &bindings);
+        assert!(!state.left && !state.fire);
     }
 }
@@ -0,0 +1,54 @@
+use std::error::Error;
+
+use sdl2::controller::GameController;
+use sdl2::event::Event;
+use sdl2::GameControllerSubsystem;
+
+// Keeps the attached gamepads open so their events reach the game. Pads
+// plugged in while the game runs are opened when SDL reports them.
+pub struct Gamepads {
+    subsystem: GameControllerSubsystem,
+    open: Vec<GameController>,
+}
+
+impl Gamepads {
+    pub fn new(sdl: &sdl2::Sdl) -> Result<Self, Box<dyn Error>> {
+        let subsystem = sdl.game_controller()?;
+        let mut gamepads = Gamepads {
+            subsystem,
+            open: Vec::new(),
+        };
+        for index in 0..gamepads.subsystem.num_joysticks()? {
+            gamepads.open(index);
+        }
+        Ok(gamepads)
+    }
+
+    pub fn update(&mut self, event: &Event) {
+        match event {
+            Event::ControllerDeviceAdded { which, .. } => self.open(*which),
+            Event::ControllerDeviceRemoved { which, .. } => {
+                self.open.retain(|pad| pad.instance_id() != *which)
+            }
+            _ => {}
+        }
+    }
+
+    fn open(&mut self, index: u32) {
+        if !self.subsystem.is_game_controller(index) {
+            return;
+        }
+        match self.subsystem.open(index) {
+            Ok(pad) => {
+                if self
+                    .open
+                    .iter()
+                    .all(|open| open.instance_id() != pad.instance_id())
+                {
+                    self.open.push(pad);
+                }
+            }
+            Err(e) => eprintln!("Failed to open gamepad {}: {}", index, e),
+        }
+    }
+}
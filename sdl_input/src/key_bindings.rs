@@ -0,0 +1,72 @@
+use sdl2::keyboard::Keycode;
+
+// The controls that can be moved to other keys
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Control {
+    Up,
+    Down,
+    Left,
+    Right,
+    Fire,
+}
+
+impl Control {
+    pub const ALL: [Control; 5] = [
+        Control::Up,
+        Control::Down,
+        Control::Left,
+        Control::Right,
+        Control::Fire,
+    ];
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct KeyBindings {
+    pub up: Keycode,
+    pub down: Keycode,
+    pub left: Keycode,
+    pub right: Keycode,
+    pub fire: Keycode,
+}
+
+impl KeyBindings {
+    pub fn key(&self, control: Control) -> Keycode {
+        match control {
+            Control::Up => self.up,
+            Control::Down => self.down,
+            Control::Left => self.left,
+            Control::Right => self.right,
+            Control::Fire => self.fire,
+        }
+    }
+
+    pub fn set(&mut self, control: Control, key: Keycode) {
+        match control {
+            Control::Up => self.up = key,
+            Control::Down => self.down = key,
+            Control::Left => self.left = key,
+            Control::Right => self.right = key,
+            Control::Fire => self.fire = key,
+        }
+    }
+
+    // The control a key is bound to, if any
+    pub(crate) fn control(&self, key: Keycode) -> Option<Control> {
+        Control::ALL
+            .iter()
+            .copied()
+            .find(|&control| self.key(control) == key)
+    }
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        KeyBindings {
+            up: Keycode::W,
+            down: Keycode::S,
+            left: Keycode::A,
+            right: Keycode::D,
+            fire: Keycode::Return,
+        }
+    }
+}
@@ -1,3 +1,7 @@
 mod control_state;
+mod gamepads;
+mod key_bindings;
 
 pub use control_state::ControlState;
+pub use gamepads::Gamepads;
+pub use key_bindings::{Control, KeyBindings};
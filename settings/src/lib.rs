@@ -0,0 +1,193 @@
+// The choices the player makes in the menus, kept in a file in the
+// platform's config directory between runs
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use localization::Language;
+use serde::{Deserialize, Serialize};
+use shared_types::{Accessibility, Difficulty, GameMode, Volumes};
+
+// The directory below the config directory and the file in it
+const APP_DIR_NAME: &str = "rideways";
+const SETTINGS_FILE: &str = "settings.json";
+
+// The window sizes accepted from the file
+const MIN_WINDOW_SIZE: (u32, u32) = (320, 160);
+const MAX_WINDOW_SIZE: u32 = 16384;
+
+// The keys are stored by their SDL names, which the game turns into key
+// codes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyNames {
+    pub up: String,
+    pub down: String,
+    pub left: String,
+    pub right: String,
+    pub fire: String,
+}
+
+impl KeyNames {
+    fn all(&self) -> [&str; 5] {
+        [&self.up, &self.down, &self.left, &self.right, &self.fire]
+    }
+}
+
+impl Default for KeyNames {
+    fn default() -> Self {
+        KeyNames {
+            up: "W".to_string(),
+            down: "S".to_string(),
+            left: "A".to_string(),
+            right: "D".to_string(),
+            fire: "Return".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub volumes: Volumes,
+    pub keys: KeyNames,
+    pub fullscreen: bool,
+    pub window_size: (u32, u32),
+    pub vsync: bool,
+    pub mode: GameMode,
+    pub difficulty: Difficulty,
+    pub accessibility: Accessibility,
+    pub language: Language,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            volumes: Volumes::default(),
+            keys: KeyNames::default(),
+            fullscreen: false,
+            window_size: (1200, 600),
+            vsync: true,
+            mode: GameMode::default(),
+            difficulty: Difficulty::default(),
+            accessibility: Accessibility::default(),
+            language: Language::default(),
+        }
+    }
+}
+
+impl Settings {
+    // Where the settings are kept, if the platform has a config directory
+    pub fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join(APP_DIR_NAME).join(SETTINGS_FILE))
+    }
+
+    pub fn parse(json: &str) -> Result<Self, Box<dyn Error>> {
+        let settings: Settings = serde_json::from_str(json)?;
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let json = std::fs::read_to_string(path)?;
+        Settings::parse(&json)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e).into())
+    }
+
+    // A missing file gives the defaults. So does a broken one, with a
+    // warning, so a bad file never keeps the game from starting.
+    pub fn load_or_default(path: &Path) -> Self {
+        if !path.exists() {
+            return Settings::default();
+        }
+        Settings::load(path).unwrap_or_else(|e| {
+            eprintln!("Using the default settings: {}", e);
+            Settings::default()
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn validate(&self) -> Result<(), Box<dyn Error>> {
+        let volumes = [
+            self.volumes.master,
+            self.volumes.music,
+            self.volumes.effects,
+        ];
+        if volumes.iter().any(|v| !(0.0..=1.0).contains(v)) {
+            return Err("Volumes must be between 0 and 1".into());
+        }
+        let (width, height) = self.window_size;
+        if width < MIN_WINDOW_SIZE.0
+            || height < MIN_WINDOW_SIZE.1
+            || width > MAX_WINDOW_SIZE
+            || height > MAX_WINDOW_SIZE
+        {
+            return Err(format!("Bad window size {}x{}", width, height).into());
+        }
+        let keys = self.keys.all();
+        if keys.iter().any(|key| key.is_empty()) {
+            return Err("A control has no key".into());
+        }
+        for (index, key) in keys.iter().enumerate() {
+            if keys[index + 1..].contains(key) {
+                return Err(format!("The key {} is used twice", key).into());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        // Saving makes the directories it needs
+        let path = dir.path().join("saved/settings.json");
+        let mut settings = Settings::default();
+        settings.volumes.music = 0.3;
+        settings.keys.fire = "Space".to_string();
+        settings.difficulty = Difficulty::Hard;
+        settings.accessibility.screen_shake = false;
+        settings.language = Language::Danish;
+        settings.save(&path).unwrap();
+        assert_eq!(Settings::load(&path).unwrap(), settings);
+    }
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let settings =
+            Settings::parse(r#"{ "fullscreen": true, "keys": { "fire": "Space" } }"#).unwrap();
+        assert!(settings.fullscreen);
+        assert_eq!(settings.keys.fire, "Space");
+        assert_eq!(settings.keys.up, "W");
+        assert_eq!(settings.volumes, Volumes::default());
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(Settings::parse("{ not json").is_err());
+        assert!(Settings::parse(r#"{ "volumes": { "master": 2.0 } }"#).is_err());
+        assert!(Settings::parse(r#"{ "window_size": [10, 10] }"#).is_err());
+        assert!(Settings::parse(r#"{ "keys": { "up": "D" } }"#).is_err());
+        assert!(Settings::parse(r#"{ "difficulty": "impossible" }"#).is_err());
+        assert!(Settings::parse(r#"{ "language": "xx" }"#).is_err());
+    }
+
+    #[test]
+    fn test_broken_file_gives_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.json");
+        assert_eq!(Settings::load_or_default(&path), Settings::default());
+        std::fs::write(&path, "{ \"vsync\": ").unwrap();
+        assert_eq!(Settings::load_or_default(&path), Settings::default());
+    }
+}
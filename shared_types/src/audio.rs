@@ -0,0 +1,358 @@
+use std::error::Error;
+
+use geometry::Position;
+use serde::{Deserialize, Serialize};
+
+use crate::Camera;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SoundEffect {
+    PlayerShot,
+    AlienShot,
+    Explosion,
+    PlayerDeath,
+}
+
+impl SoundEffect {
+    // The most copies of the sound that may play at the same time, so a
+    // burst of shots doesn't drown everything else
+    pub fn max_voices(&self) -> usize {
+        match self {
+            SoundEffect::PlayerShot => 3,
+            SoundEffect::AlienShot => 4,
+            SoundEffect::Explosion => 6,
+            SoundEffect::PlayerDeath => 1,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MusicTrack {
+    Title,
+    Playing,
+}
+
+// The files in the asset directory holding the sounds
+pub const SOUND_FILES: &[(SoundEffect, &str)] = &[
+    (SoundEffect::PlayerShot, "sounds/player_shot.wav"),
+    (SoundEffect::AlienShot, "sounds/alien_shot.wav"),
+    (SoundEffect::Explosion, "sounds/explosion.wav"),
+    (SoundEffect::PlayerDeath, "sounds/player_death.wav"),
+];
+
+pub const MUSIC_FILES: &[(MusicTrack, &str)] = &[
+    (MusicTrack::Title, "music/title.wav"),
+    (MusicTrack::Playing, "music/playing.wav"),
+];
+
+// Every file the sounds and music are made from
+pub fn sound_asset_files() -> impl Iterator<Item = &'static str> {
+    let effects = SOUND_FILES.iter().map(|(_, file)| *file);
+    effects.chain(MUSIC_FILES.iter().map(|(_, file)| *file))
+}
+
+// A request to play a sound effect. Sounds with a position are panned to
+// where the position is on the screen.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PlaySound {
+    pub id: SoundEffect,
+    pub position: Option<Position>,
+}
+
+// Sound effects to play at the end of the frame. Systems add to it and an
+// audio backend empties it.
+#[derive(Debug, Default)]
+pub struct AudioQueue {
+    commands: Vec<PlaySound>,
+}
+
+impl AudioQueue {
+    pub fn play(&mut self, id: SoundEffect) {
+        self.commands.push(PlaySound { id, position: None });
+    }
+
+    pub fn play_at(&mut self, id: SoundEffect, position: Position) {
+        self.commands.push(PlaySound {
+            id,
+            position: Some(position),
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    // Plays the queued sounds on the backend, leaving the queue empty. Sounds
+    // are panned by where the camera shows them, from -1 at the left edge of
+    // the screen to 1 at the right edge. Sounds that already have as many
+    // voices playing as they may are skipped. A sound that fails to play doesn't stop the rest;
+    // the first error is returned once they have all been tried.
+    pub fn drain_into(
+        &mut self,
+        backend: &mut dyn AudioBackend,
+        camera: &Camera,
+    ) -> Result<(), Box<dyn Error>> {
+        let screen_width = camera.viewport().0;
+        let mut first_error = None;
+        for command in self.commands.drain(..) {
+            if backend.voices_playing(command.id) >= command.id.max_voices() {
+                continue;
+            }
+            let pan = command.position.map_or(0.0, |position| {
+                let screen_x = camera.world_to_screen(position).0;
+                (screen_x / screen_width * 2.0 - 1.0).clamp(-1.0, 1.0)
+            });
+            if let Err(error) = backend.play(command.id, pan) {
+                first_error.get_or_insert(error);
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+}
+
+// Something that can make sound. The game talks to this instead of to a
+// sound library, so it can run, and be tested, without one.
+pub trait AudioBackend {
+    // Pan goes from -1 for all left to 1 for all right
+    fn play(&mut self, id: SoundEffect, pan: f32) -> Result<(), Box<dyn Error>>;
+
+    // How many copies of the sound are playing right now
+    fn voices_playing(&self, id: SoundEffect) -> usize;
+
+    // Starts looping the track, unless it is already playing
+    fn play_music(&mut self, track: MusicTrack) -> Result<(), Box<dyn Error>>;
+
+    fn set_volumes(&mut self, volumes: Volumes);
+
+    // Loads the sound or music from the changed file again
+    fn reload(&mut self, file: &str) -> Result<(), Box<dyn Error>>;
+}
+
+// Plays nothing, for when there is no sound device
+pub struct NullAudio;
+
+impl AudioBackend for NullAudio {
+    fn play(&mut self, _id: SoundEffect, _pan: f32) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    fn voices_playing(&self, _id: SoundEffect) -> usize {
+        0
+    }
+
+    fn play_music(&mut self, _track: MusicTrack) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    fn set_volumes(&mut self, _volumes: Volumes) {}
+
+    fn reload(&mut self, _file: &str) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+}
+
+// Remembers what it was asked to play, for tests. Every sound played keeps
+// playing until finish_sounds is called.
+#[derive(Debug, Default)]
+pub struct RecordingAudio {
+    pub played: Vec<(SoundEffect, f32)>,
+    pub music: Option<MusicTrack>,
+    pub volumes: Volumes,
+}
+
+impl RecordingAudio {
+    pub fn finish_sounds(&mut self) {
+        self.played.clear();
+    }
+}
+
+impl AudioBackend for RecordingAudio {
+    fn play(&mut self, id: SoundEffect, pan: f32) -> Result<(), Box<dyn Error>> {
+        self.played.push((id, pan));
+        Ok(())
+    }
+
+    fn voices_playing(&self, id: SoundEffect) -> usize {
+        self.played
+            .iter()
+            .filter(|(played, _)| *played == id)
+            .count()
+    }
+
+    fn play_music(&mut self, track: MusicTrack) -> Result<(), Box<dyn Error>> {
+        self.music = Some(track);
+        Ok(())
+    }
+
+    fn set_volumes(&mut self, volumes: Volumes) {
+        self.volumes = volumes;
+    }
+
+    fn reload(&mut self, _file: &str) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+}
+
+// Loudness from 0 to 1. The music and effects volumes are relative to the
+// master volume.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Volumes {
+    pub master: f32,
+    pub music: f32,
+    pub effects: f32,
+}
+
+impl Volumes {
+    pub fn music_level(&self) -> f32 {
+        (self.master * self.music).clamp(0.0, 1.0)
+    }
+
+    pub fn effects_level(&self) -> f32 {
+        (self.master * self.effects).clamp(0.0, 1.0)
+    }
+}
+
+impl Default for Volumes {
+    fn default() -> Self {
+        Volumes {
+            master: 1.0,
+            music: 0.6,
+            effects: 1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geometry::RectSize;
+    use std::path::Path;
+
+    fn camera() -> Camera {
+        Camera::new(RectSize(400.0, 300.0))
+    }
+
+    #[test]
+    fn test_sound_files_exist() {
+        let assets = Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets");
+        for file in sound_asset_files() {
+            assert!(assets.join(file).is_file(), "{} is missing", file);
+        }
+    }
+
+    #[test]
+    fn test_queue_pans_by_position() {
+        let mut queue = AudioQueue::default();
+        let mut audio = RecordingAudio::default();
+        queue.play_at(SoundEffect::Explosion, Position(0.0, 100.0));
+        queue.play_at(SoundEffect::Explosion, Position(300.0, 100.0));
+        queue.play_at(SoundEffect::Explosion, Position(2000.0, 100.0));
+        queue.play(SoundEffect::PlayerDeath);
+        queue.drain_into(&mut audio, &camera()).unwrap();
+        assert!(queue.is_empty());
+        assert_eq!(
+            audio.played,
+            vec![
+                (SoundEffect::Explosion, -1.0),
+                (SoundEffect::Explosion, 0.5),
+                (SoundEffect::Explosion, 1.0),
+                (SoundEffect::PlayerDeath, 0.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_queue_pans_by_screen_position() {
+        let mut queue = AudioQueue::default();
+        let mut audio = RecordingAudio::default();
+        let mut camera = camera();
+        camera.zoom = 2.0;
+        queue.play_at(SoundEffect::Explosion, Position(250.0, 100.0));
+        queue.play_at(SoundEffect::Explosion, Position(300.0, 100.0));
+        queue.drain_into(&mut audio, &camera).unwrap();
+        assert_eq!(
+            audio.played,
+            vec![(SoundEffect::Explosion, 0.5), (SoundEffect::Explosion, 1.0)]
+        );
+    }
+
+    #[test]
+    fn test_voice_cap() {
+        let mut queue = AudioQueue::default();
+        let mut audio = RecordingAudio::default();
+        for _ in 0..10 {
+            queue.play(SoundEffect::PlayerShot);
+        }
+        queue.drain_into(&mut audio, &camera()).unwrap();
+        assert_eq!(
+            audio.voices_playing(SoundEffect::PlayerShot),
+            SoundEffect::PlayerShot.max_voices()
+        );
+
+        queue.play(SoundEffect::PlayerShot);
+        queue.drain_into(&mut audio, &camera()).unwrap();
+        assert_eq!(audio.played.len(), SoundEffect::PlayerShot.max_voices());
+        audio.finish_sounds();
+        queue.play(SoundEffect::PlayerShot);
+        queue.drain_into(&mut audio, &camera()).unwrap();
+        assert_eq!(audio.played.len(), 1);
+    }
+
+    // Fails to play explosions, and plays everything else
+    #[derive(Default)]
+    struct NoExplosions {
+        played: Vec<SoundEffect>,
+    }
+
+    impl AudioBackend for NoExplosions {
+        fn play(&mut self, id: SoundEffect, _pan: f32) -> Result<(), Box<dyn Error>> {
+            if id == SoundEffect::Explosion {
+                return Err("no explosions".into());
+            }
+            self.played.push(id);
+            Ok(())
+        }
+
+        fn voices_playing(&self, _id: SoundEffect) -> usize {
+            0
+        }
+
+        fn play_music(&mut self, _track: MusicTrack) -> Result<(), Box<dyn Error>> {
+            Ok(())
+        }
+
+        fn set_volumes(&mut self, _volumes: Volumes) {}
+
+        fn reload(&mut self, _file: &str) -> Result<(), Box<dyn Error>> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_failed_sound_doesnt_drop_the_rest() {
+        let mut queue = AudioQueue::default();
+        let mut audio = NoExplosions::default();
+        queue.play(SoundEffect::PlayerShot);
+        queue.play(SoundEffect::Explosion);
+        queue.play(SoundEffect::PlayerDeath);
+        let error = queue.drain_into(&mut audio, &camera()).unwrap_err();
+        assert_eq!(error.to_string(), "no explosions");
+        assert!(queue.is_empty());
+        assert_eq!(
+            audio.played,
+            vec![SoundEffect::PlayerShot, SoundEffect::PlayerDeath]
+        );
+    }
+
+    #[test]
+    fn test_volume_levels() {
+        let volumes = Volumes {
+            master: 0.5,
+            music: 0.5,
+            effects: 2.0,
+        };
+        assert_eq!(volumes.music_level(), 0.25);
+        assert_eq!(volumes.effects_level(), 1.0);
+    }
+}
@@ -0,0 +1,163 @@
+use geometry::{Position, RectSize, Vec2};
+
+// Turns world coordinates into screen coordinates. The camera follows a
+// target smoothly, can zoom around the center of the screen and shakes
+// with an amount given by its trauma. Trauma is added by events like the
+// player dying, and fades out by itself.
+#[derive(Debug, Clone)]
+pub struct Camera {
+    viewport: RectSize,
+    pub zoom: f32,
+    // How far towards the target the camera moves, 0 keeps it still and 1
+    // keeps the target centered
+    pub follow_amount: f32,
+    // How quickly the camera catches up with the target, per second
+    pub follow_speed: f32,
+    // The largest shake offset in pixels, used at full trauma
+    pub max_shake: f32,
+    // Trauma lost per second
+    pub trauma_decay: f32,
+    trauma: f32,
+    follow_offset: Vec2,
+    shake_offset: Vec2,
+}
+
+impl Camera {
+    pub fn new(viewport: RectSize) -> Self {
+        Camera {
+            viewport,
+            zoom: 1.0,
+            follow_amount: 0.1,
+            follow_speed: 2.0,
+            max_shake: 25.0,
+            trauma_decay: 1.0,
+            trauma: 0.0,
+            follow_offset: Vec2::ZERO,
+            shake_offset: Vec2::ZERO,
+        }
+    }
+
+    pub fn viewport(&self) -> RectSize {
+        self.viewport
+    }
+
+    fn screen_center(&self) -> Position {
+        Position(self.viewport.0 / 2.0, self.viewport.1 / 2.0)
+    }
+
+    pub fn trauma(&self) -> f32 {
+        self.trauma
+    }
+
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).clamp(0.0, 1.0);
+    }
+
+    // Stops shaking and centers the camera again
+    pub fn reset(&mut self) {
+        self.trauma = 0.0;
+        self.follow_offset = Vec2::ZERO;
+        self.shake_offset = Vec2::ZERO;
+    }
+
+    // Moves the camera seconds forward. shake is a random vector with both
+    // coordinates between -1 and 1, deciding the direction of this frame's
+    // shake.
+    pub fn update(&mut self, seconds: f32, target: Option<Position>, shake: Vec2) {
+        let wanted_offset = target.map_or(Vec2::ZERO, |target| {
+            (target - self.screen_center()) * self.follow_amount
+        });
+        let catch_up = 1.0 - (-self.follow_speed * seconds).exp();
+        self.follow_offset = self.follow_offset.lerp(wanted_offset, catch_up);
+
+        // Squaring the trauma makes small amounts barely noticeable while
+        // large amounts give a violent shake
+        self.shake_offset = shake * (self.max_shake * self.trauma * self.trauma);
+        self.trauma = (self.trauma - self.trauma_decay * seconds).max(0.0);
+    }
+
+    // The point in the world that is shown in the center of the screen
+    pub fn view_center(&self) -> Position {
+        self.screen_center() + self.follow_offset + self.shake_offset
+    }
+
+    pub fn world_to_screen(&self, world: Position) -> Position {
+        self.screen_center() + (world - self.view_center()) * self.zoom
+    }
+
+    pub fn screen_to_world(&self, screen: Position) -> Position {
+        self.view_center() + (screen - self.screen_center()) / self.zoom
+    }
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Camera::new(RectSize(0.0, 0.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera() -> Camera {
+        Camera::new(RectSize(1200.0, 600.0))
+    }
+
+    #[test]
+    fn test_still_camera_is_identity() {
+        let camera = camera();
+        let p = Position(100.0, 50.0);
+        assert_eq!(camera.world_to_screen(p), p);
+        assert_eq!(camera.screen_to_world(p), p);
+    }
+
+    #[test]
+    fn test_zoom_around_screen_center() {
+        let mut camera = camera();
+        camera.zoom = 2.0;
+        assert_eq!(
+            camera.world_to_screen(Position(600.0, 300.0)),
+            Position(600.0, 300.0)
+        );
+        assert_eq!(
+            camera.world_to_screen(Position(700.0, 250.0)),
+            Position(800.0, 200.0)
+        );
+        assert_eq!(
+            camera.screen_to_world(Position(800.0, 200.0)),
+            Position(700.0, 250.0)
+        );
+    }
+
+    #[test]
+    fn test_follow_moves_smoothly_towards_target() {
+        let mut camera = camera();
+        camera.follow_amount = 0.5;
+        let target = Position(800.0, 300.0);
+        camera.update(0.1, Some(target), Vec2::ZERO);
+        let first = camera.view_center().0;
+        assert!(first > 600.0 && first < 700.0);
+        for _ in 0..100 {
+            camera.update(0.1, Some(target), Vec2::ZERO);
+        }
+        assert!((camera.view_center().0 - 700.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_trauma_shakes_and_decays() {
+        let mut camera = camera();
+        camera.add_trauma(0.5);
+        camera.add_trauma(0.8);
+        assert_eq!(camera.trauma(), 1.0);
+        camera.update(0.25, None, Vec2::new(1.0, -1.0));
+        assert_eq!(camera.view_center(), Position(625.0, 275.0));
+        assert_eq!(camera.trauma(), 0.75);
+        for _ in 0..4 {
+            camera.update(0.25, None, Vec2::new(1.0, -1.0));
+        }
+        assert_eq!(camera.trauma(), 0.0);
+        camera.update(0.25, None, Vec2::new(1.0, -1.0));
+        assert_eq!(camera.view_center(), Position(600.0, 300.0));
+    }
+}
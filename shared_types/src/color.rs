@@ -0,0 +1,63 @@
+use serde::Deserialize;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    #[serde(default = "opaque")]
+    pub a: u8,
+}
+
+fn opaque() -> u8 {
+    255
+}
+
+impl Color {
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Color { r, g, b, a: 255 }
+    }
+
+    pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Color { r, g, b, a }
+    }
+
+    // Scales the red, green and blue parts, leaving alpha as it is
+    pub fn scaled(&self, factor: f32) -> Color {
+        let scale = |c: u8| (c as f32 * factor).round().clamp(0.0, 255.0) as u8;
+        Color::rgba(scale(self.r), scale(self.g), scale(self.b), self.a)
+    }
+
+    // Multiplies each part, including alpha, with the other color's
+    pub fn tinted(&self, other: Color) -> Color {
+        let mul = |a: u8, b: u8| (a as u16 * b as u16 / 255) as u8;
+        Color::rgba(
+            mul(self.r, other.r),
+            mul(self.g, other.g),
+            mul(self.b, other.b),
+            mul(self.a, other.a),
+        )
+    }
+
+    // Scales the alpha, leaving the color as it is
+    pub fn faded(&self, factor: f32) -> Color {
+        let a = (self.a as f32 * factor).round().clamp(0.0, 255.0) as u8;
+        Color::rgba(self.r, self.g, self.b, a)
+    }
+
+    // Blends from this color to the other, including alpha, as t goes from
+    // 0 to 1
+    pub fn lerp(&self, other: Color, t: f32) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+        Color::rgba(
+            mix(self.r, other.r),
+            mix(self.g, other.g),
+            mix(self.b, other.b),
+            mix(self.a, other.a),
+        )
+    }
+}
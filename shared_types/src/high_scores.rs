@@ -0,0 +1,45 @@
+// How many of the best scores are kept
+const HIGH_SCORE_COUNT: usize = 5;
+
+// The best scores, highest first
+#[derive(Debug, Default, Clone)]
+pub struct HighScores {
+    scores: Vec<u32>,
+}
+
+impl HighScores {
+    // Returns whether the score made it onto the list
+    pub fn add(&mut self, score: u32) -> bool {
+        if score == 0 {
+            return false;
+        }
+        let index = self.scores.partition_point(|&best| best >= score);
+        if index >= HIGH_SCORE_COUNT {
+            return false;
+        }
+        self.scores.insert(index, score);
+        self.scores.truncate(HIGH_SCORE_COUNT);
+        true
+    }
+
+    pub fn scores(&self) -> &[u32] {
+        &self.scores
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keeps_best_scores() {
+        let mut high_scores = HighScores::default();
+        assert!(!high_scores.add(0));
+        for score in &[30, 10, 50, 20, 40] {
+            assert!(high_scores.add(*score));
+        }
+        assert!(!high_scores.add(5));
+        assert!(high_scores.add(35));
+        assert_eq!(high_scores.scores(), &[50, 40, 35, 30, 20]);
+    }
+}
@@ -1,7 +1,23 @@
+mod audio;
+mod camera;
+mod color;
 mod entity_sizes;
+mod high_scores;
+mod run_stats;
+mod system_times;
 use geometry::Rect;
+use serde::{Deserialize, Serialize};
 
+pub use audio::{
+    sound_asset_files, AudioBackend, AudioQueue, MusicTrack, NullAudio, PlaySound, RecordingAudio,
+    SoundEffect, Volumes, MUSIC_FILES, SOUND_FILES,
+};
+pub use camera::Camera;
+pub use color::Color;
 pub use entity_sizes::EntitySizes;
+pub use high_scores::HighScores;
+pub use run_stats::RunStats;
+pub use system_times::SystemTimes;
 
 #[derive(Default, Debug)]
 pub struct ElapsedSeconds(pub f32);
@@ -9,18 +25,37 @@ pub struct ElapsedSeconds(pub f32);
 #[derive(Default)]
 pub struct Arena(pub Rect);
 
+// The score multiplier, boss health, weapon level and wave are shown on
+// the HUD while present. The game doesn't have any of them yet, so nothing
+// inserts these so far.
+#[derive(Debug, Copy, Clone)]
+pub struct ScoreMultiplier {
+    pub value: u32,
+    // How far towards the next multiplier, from 0 to 1
+    pub progress: f32,
+}
+
+// The health of the boss, from 0 to 1
+#[derive(Debug, Copy, Clone)]
+pub struct BossHealth(pub f32);
+
+#[derive(Debug, Copy, Clone)]
+pub struct WeaponLevel(pub u32);
+
+#[derive(Debug, Copy, Clone)]
+pub struct Wave(pub u32);
+
 #[derive(Copy, Clone)]
 pub enum GameState {
-    Idle { button_pressed: bool },
+    Menu,
     Playing { state: PlayingGameState },
+    // Fire is ignored until seconds_left has run out
     GameOver { seconds_left: f64 },
 }
 
 impl GameState {
     pub fn new() -> Self {
-        GameState::Idle {
-            button_pressed: false,
-        }
+        GameState::Menu
     }
 }
 
@@ -30,6 +65,65 @@ impl Default for GameState {
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameMode {
+    #[default]
+    Normal,
+    // Only one life
+    Hardcore,
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 2] = [GameMode::Normal, GameMode::Hardcore];
+
+    pub fn lives(&self) -> i32 {
+        match self {
+            GameMode::Normal => 3,
+            GameMode::Hardcore => 1,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    // How often enemies appear compared to normal
+    pub fn enemy_rate(&self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.6,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.5,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Accessibility {
+    pub screen_shake: bool,
+    // Keeps the stars from twinkling
+    pub reduce_flashing: bool,
+}
+
+impl Default for Accessibility {
+    fn default() -> Self {
+        Accessibility {
+            screen_shake: true,
+            reduce_flashing: false,
+        }
+    }
+}
+
 #[derive(Default, Copy, Clone)]
 pub struct PlayingGameState {
     pub score: u32,
@@ -38,9 +132,13 @@ pub struct PlayingGameState {
 
 impl PlayingGameState {
     pub fn new() -> PlayingGameState {
+        Self::for_mode(GameMode::Normal)
+    }
+
+    pub fn for_mode(mode: GameMode) -> PlayingGameState {
         PlayingGameState {
             score: 0,
-            lives_left: 3,
+            lives_left: mode.lives(),
         }
     }
 
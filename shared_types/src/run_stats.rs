@@ -0,0 +1,54 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+// What happened during one game, from the first life to the last
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RunStats {
+    pub shots_fired: u32,
+    // Player shots that hit an alien
+    pub hits: u32,
+    // Aliens killed, by the kind of alien
+    pub aliens_killed: BTreeMap<String, u32>,
+    pub seconds_survived: f32,
+    pub deaths: u32,
+}
+
+impl RunStats {
+    pub fn add_kill(&mut self, kind: &str) {
+        *self.aliens_killed.entry(kind.to_string()).or_insert(0) += 1;
+    }
+
+    pub fn total_kills(&self) -> u32 {
+        self.aliens_killed.values().sum()
+    }
+
+    // The part of the shots that hit, from 0 to 1
+    pub fn accuracy(&self) -> f32 {
+        if self.shots_fired == 0 {
+            0.0
+        } else {
+            self.hits as f32 / self.shots_fired as f32
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kills_and_accuracy() {
+        let mut stats = RunStats::default();
+        assert_eq!(stats.accuracy(), 0.0);
+        stats.shots_fired = 8;
+        stats.hits = 2;
+        stats.add_kill("ufo");
+        stats.add_kill("ufo");
+        stats.add_kill("boss");
+        assert_eq!(stats.accuracy(), 0.25);
+        assert_eq!(stats.aliens_killed["ufo"], 2);
+        assert_eq!(stats.total_kills(), 3);
+    }
+}
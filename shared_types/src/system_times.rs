@@ -0,0 +1,43 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+// How much each new time counts in the smoothed time
+const SMOOTHING: f32 = 0.1;
+
+// How long each system takes to run, in seconds, smoothed over frames. The
+// systems record into clones that share the same times, so they can run in
+// parallel without fetching this as a resource.
+#[derive(Debug, Clone, Default)]
+pub struct SystemTimes(Arc<Mutex<BTreeMap<&'static str, f32>>>);
+
+impl SystemTimes {
+    pub fn record(&self, system: &'static str, seconds: f32) {
+        let mut times = self.0.lock().expect("system times poisoned");
+        let time = times.entry(system).or_insert(seconds);
+        *time += (seconds - *time) * SMOOTHING;
+    }
+
+    // The times by system name
+    pub fn times(&self) -> Vec<(&'static str, f32)> {
+        let times = self.0.lock().expect("system times poisoned");
+        times.iter().map(|(name, time)| (*name, *time)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shared_and_smoothed() {
+        let times = SystemTimes::default();
+        let recorder = times.clone();
+        recorder.record("B", 0.002);
+        recorder.record("A", 0.001);
+        recorder.record("A", 0.011);
+        let recorded = times.times();
+        assert_eq!(recorded[0].0, "A");
+        assert!((recorded[0].1 - 0.002).abs() < 1e-6);
+        assert_eq!(recorded[1], ("B", 0.002));
+    }
+}
@@ -0,0 +1,151 @@
+use geometry::Position;
+use image::{Rgba, RgbaImage};
+use render::{BlendMode, Color, FrameRect, SpriteOptions};
+
+pub struct Canvas {
+    image: RgbaImage,
+}
+
+fn blend(dest: &mut Rgba<u8>, source: Rgba<u8>, blend_mode: BlendMode) {
+    let alpha = source[3] as u32;
+    for channel in 0..3 {
+        let s = source[channel] as u32;
+        let d = dest[channel] as u32;
+        dest[channel] = match blend_mode {
+            BlendMode::Blend => ((s * alpha + d * (255 - alpha)) / 255) as u8,
+            BlendMode::Add => (d + s * alpha / 255).min(255) as u8,
+        };
+    }
+    if blend_mode == BlendMode::Blend {
+        dest[3] = (alpha + dest[3] as u32 * (255 - alpha) / 255) as u8;
+    }
+}
+
+impl Canvas {
+    pub fn new(size: (u32, u32)) -> Self {
+        Canvas {
+            image: RgbaImage::new(size.0, size.1),
+        }
+    }
+
+    pub fn image(&self) -> &RgbaImage {
+        &self.image
+    }
+
+    pub fn fill(&mut self, color: Color) {
+        for pixel in self.image.pixels_mut() {
+            *pixel = Rgba([color.r, color.g, color.b, color.a]);
+        }
+    }
+
+    fn blend_pixel(&mut self, x: i32, y: i32, source: Rgba<u8>, blend_mode: BlendMode) {
+        if x < 0 || y < 0 || x >= self.image.width() as i32 || y >= self.image.height() as i32 {
+            return;
+        }
+        blend(
+            self.image.get_pixel_mut(x as u32, y as u32),
+            source,
+            blend_mode,
+        );
+    }
+
+    pub fn fill_circle(&mut self, center_x: i32, center_y: i32, radius: i32, color: Color) {
+        let source = Rgba([color.r, color.g, color.b, color.a]);
+        for y in -radius..=radius {
+            for x in -radius..=radius {
+                if x * x + y * y <= radius * radius {
+                    self.blend_pixel(center_x + x, center_y + y, source, BlendMode::Blend);
+                }
+            }
+        }
+    }
+
+    pub fn fill_rect(&mut self, left: i32, top: i32, width: u32, height: u32, color: Color) {
+        let source = Rgba([color.r, color.g, color.b, color.a]);
+        for y in top..top + height as i32 {
+            for x in left..left + width as i32 {
+                self.blend_pixel(x, y, source, BlendMode::Blend);
+            }
+        }
+    }
+
+    // Steps one pixel at a time along the longest axis of the line
+    pub fn draw_line(&mut self, from: (f32, f32), to: (f32, f32), color: Color) {
+        let source = Rgba([color.r, color.g, color.b, color.a]);
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let steps = dx.abs().max(dy.abs()).round().max(1.0) as i32;
+        for step in 0..=steps {
+            let t = step as f32 / steps as f32;
+            let x = (from.0 + dx * t).round() as i32;
+            let y = (from.1 + dy * t).round() as i32;
+            self.blend_pixel(x, y, source, BlendMode::Blend);
+        }
+    }
+
+    // Draws the frame part of the image with its top left corner at topleft.
+    // A rotated or scaled frame is drawn by mapping every pixel of the area
+    // it can cover back into the source image, picking the nearest source
+    // pixel.
+    pub fn draw_image(
+        &mut self,
+        image: &RgbaImage,
+        frame: FrameRect,
+        topleft: Position,
+        options: &SpriteOptions,
+        blend_mode: BlendMode,
+    ) {
+        let (width, height) = (frame.w, frame.h);
+        let tint = options.tint;
+        let source_pixel = |x: u32, y: u32| {
+            let pixel = image.get_pixel(frame.x + x, frame.y + y);
+            let mul = |c: u8, t: u8| (c as u16 * t as u16 / 255) as u8;
+            Rgba([
+                mul(pixel[0], tint.r),
+                mul(pixel[1], tint.g),
+                mul(pixel[2], tint.b),
+                mul(pixel[3], tint.a),
+            ])
+        };
+        let scale = options.scale;
+        let rotation = options.rotation.as_ref();
+        let degrees = rotation.map_or(0.0, |r| r.degrees);
+        if degrees == 0.0 && scale == 1.0 {
+            let left = topleft.0 as i32;
+            let top = topleft.1 as i32;
+            for y in 0..height {
+                for x in 0..width {
+                    let pixel = source_pixel(x, y);
+                    self.blend_pixel(left + x as i32, top + y as i32, pixel, blend_mode);
+                }
+            }
+            return;
+        }
+        if scale <= 0.0 {
+            return;
+        }
+
+        let pivot = rotation
+            .and_then(|r| r.pivot)
+            .unwrap_or_else(|| Position(width as f32 / 2.0, height as f32 / 2.0));
+        let pivot_x = topleft.0 + pivot.0 * scale;
+        let pivot_y = topleft.1 + pivot.1 * scale;
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        let reach = ((width as f32).hypot(height as f32) * scale).ceil() as i32;
+        for y in (pivot_y as i32 - reach)..=(pivot_y as i32 + reach) {
+            for x in (pivot_x as i32 - reach)..=(pivot_x as i32 + reach) {
+                let dx = x as f32 + 0.5 - pivot_x;
+                let dy = y as f32 + 0.5 - pivot_y;
+                let source_x = ((dx * cos + dy * sin) / scale + pivot.0).floor();
+                let source_y = ((-dx * sin + dy * cos) / scale + pivot.1).floor();
+                if source_x >= 0.0
+                    && source_y >= 0.0
+                    && source_x < width as f32
+                    && source_y < height as f32
+                {
+                    let pixel = source_pixel(source_x as u32, source_y as u32);
+                    self.blend_pixel(x, y, pixel, blend_mode);
+                }
+            }
+        }
+    }
+}
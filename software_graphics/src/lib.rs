@@ -0,0 +1,209 @@
+// A renderer drawing into an RGBA buffer in memory instead of a window. It
+// needs neither a display nor SDL, so it can be used for testing rendering.
+mod canvas;
+mod text;
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use image::RgbaImage;
+
+use ecs_components::Sprite;
+use geometry::{Position, Rect};
+use render::{
+    sprite_files_using, Atlas, BlendMode, Color, FontConfig, FontType, Renderer, Screen,
+    SpriteOptions, TextCache, TextPosition, SPRITE_FILES,
+};
+
+use canvas::Canvas;
+use text::Fonts;
+
+// Rendered text is kept for this many frames after it was last drawn
+const TEXT_CACHE_FRAMES: u64 = 60;
+
+pub struct SoftwareRenderer {
+    canvas: Canvas,
+    sprite_dir: PathBuf,
+    sprites: BTreeMap<Sprite, (RgbaImage, Atlas, BlendMode)>,
+    fonts: Option<Fonts>,
+    // Text is rendered in white and tinted when drawn, so fading text
+    // doesn't need rendering again
+    text_cache: TextCache<(String, FontType), RgbaImage>,
+    frames_presented: u32,
+}
+
+impl SoftwareRenderer {
+    // Creates a renderer with a buffer of the given size, loading the
+    // sprite images from sprite_dir
+    pub fn new(size: (u32, u32), sprite_dir: &Path) -> Result<Self, Box<dyn Error>> {
+        let mut renderer = SoftwareRenderer {
+            canvas: Canvas::new(size),
+            sprite_dir: sprite_dir.to_path_buf(),
+            sprites: BTreeMap::new(),
+            fonts: None,
+            text_cache: TextCache::new(TEXT_CACHE_FRAMES),
+            frames_presented: 0,
+        };
+        for (sprite, filename, blend_mode) in SPRITE_FILES {
+            renderer.load_sprite(*sprite, filename, *blend_mode)?;
+        }
+        Ok(renderer)
+    }
+
+    fn load_sprite(
+        &mut self,
+        sprite: Sprite,
+        filename: &str,
+        blend_mode: BlendMode,
+    ) -> Result<(), Box<dyn Error>> {
+        let path = self.sprite_dir.join(filename);
+        let image = image::open(&path)
+            .map_err(|e| format!("Failed to load {}: {}", path.display(), e))?
+            .to_rgba8();
+        let atlas = Atlas::load(&path, image.dimensions())?;
+        self.sprites.insert(sprite, (image, atlas, blend_mode));
+        Ok(())
+    }
+
+    // A renderer that draws all text with the font bundled in asset_dir,
+    // whatever fonts the system has, so tests draw the same everywhere
+    pub fn with_bundled_font(size: (u32, u32), asset_dir: &Path) -> Result<Self, Box<dyn Error>> {
+        let mut renderer = SoftwareRenderer::new(size, asset_dir)?;
+        let mut fonts = FontConfig::default();
+        fonts.prefer_file(&asset_dir.join("fonts/DejaVuSans-Bold.ttf"));
+        renderer.load_fonts(&fonts)?;
+        Ok(renderer)
+    }
+
+    // Text can only be drawn once the fonts have been loaded
+    pub fn load_fonts(&mut self, config: &FontConfig) -> Result<(), Box<dyn Error>> {
+        self.fonts = Some(Fonts::new(config)?);
+        Ok(())
+    }
+
+    pub fn image(&self) -> &RgbaImage {
+        self.canvas.image()
+    }
+
+    pub fn save_png(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        self.canvas.image().save(path)?;
+        Ok(())
+    }
+
+    pub fn frames_presented(&self) -> u32 {
+        self.frames_presented
+    }
+}
+
+impl Renderer for SoftwareRenderer {
+    fn screen(&self) -> Screen {
+        Screen::new(self.canvas.image().dimensions())
+    }
+
+    fn clear(&mut self) {
+        self.canvas.fill(Color::BLACK);
+    }
+
+    fn present(&mut self) {
+        self.frames_presented += 1;
+        self.text_cache.end_frame();
+    }
+
+    fn draw_sprite(
+        &mut self,
+        sprite: &Sprite,
+        topleft: Position,
+        options: &SpriteOptions,
+    ) -> Result<(), Box<dyn Error>> {
+        let (image, atlas, blend_mode) = self
+            .sprites
+            .get(sprite)
+            .ok_or_else(|| format!("Failed to get render info for {:?}", sprite))?;
+        self.canvas.draw_image(
+            image,
+            atlas.frame(options.frame),
+            topleft,
+            options,
+            *blend_mode,
+        );
+        Ok(())
+    }
+
+    fn draw_circle(&mut self, x: f32, y: f32, radius: i16, color: Color) {
+        self.canvas
+            .fill_circle(x as i32, y as i32, radius as i32, color);
+    }
+
+    fn fill_rect(&mut self, rect: Rect, color: Color) {
+        self.canvas.fill_rect(
+            rect.left().round() as i32,
+            rect.top().round() as i32,
+            rect.width().round() as u32,
+            rect.height().round() as u32,
+            color,
+        );
+    }
+
+    fn draw_line(&mut self, from: Position, to: Position, color: Color) {
+        self.canvas.draw_line((from.0, from.1), (to.0, to.1), color);
+    }
+
+    fn draw_text(
+        &mut self,
+        text: &str,
+        text_position: TextPosition,
+        color: Color,
+        font_type: FontType,
+    ) -> Result<(), Box<dyn Error>> {
+        let fonts = self.fonts.as_ref().ok_or("No font loaded")?;
+        let key = (text.to_string(), font_type);
+        let rendered = self
+            .text_cache
+            .get_or_insert_with(key, || fonts.render(text, &font_type, Color::WHITE));
+        let (width, height) = rendered.dimensions();
+        let whole = Atlas::single_frame((width, height)).frame(0);
+        let topleft = match text_position {
+            TextPosition::Center(x, y) => {
+                (x as i32 - width as i32 / 2, y as i32 - height as i32 / 2)
+            }
+            TextPosition::TopLeft(x, y) => (x as i32, y as i32),
+            TextPosition::TopRight(x, y) => (x as i32 - width as i32, y as i32),
+        };
+        self.canvas.draw_image(
+            rendered,
+            whole,
+            Position(topleft.0 as f32, topleft.1 as f32),
+            &SpriteOptions {
+                tint: color,
+                ..SpriteOptions::default()
+            },
+            BlendMode::Blend,
+        );
+        Ok(())
+    }
+
+    fn text_size(&mut self, text: &str, font_type: FontType) -> Result<(u32, u32), Box<dyn Error>> {
+        let fonts = self.fonts.as_ref().ok_or("No font loaded")?;
+        let key = (text.to_string(), font_type);
+        let rendered = self
+            .text_cache
+            .get_or_insert_with(key, || fonts.render(text, &font_type, Color::WHITE));
+        Ok(rendered.dimensions())
+    }
+
+    fn reload(&mut self, file: &str) -> Result<(), Box<dyn Error>> {
+        for (sprite, filename, blend_mode) in sprite_files_using(file) {
+            self.load_sprite(*sprite, filename, *blend_mode)?;
+        }
+        Ok(())
+    }
+
+    fn sprite_size(&self, sprite: &Sprite) -> Result<(u32, u32), Box<dyn Error>> {
+        let (_, atlas, _) = self
+            .sprites
+            .get(sprite)
+            .ok_or_else(|| format!("Missing render info for {:?}", sprite))?;
+        Ok(atlas.frame_size())
+    }
+}
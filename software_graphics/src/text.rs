@@ -0,0 +1,91 @@
+use std::error::Error;
+
+use fontdue::{Font, FontSettings, LineMetrics};
+use image::{Rgba, RgbaImage};
+use render::{Color, FontChoice, FontConfig, FontType};
+
+struct LoadedFont {
+    font: Font,
+    size: f32,
+    line_metrics: LineMetrics,
+}
+
+fn load_font(choice: &FontChoice) -> Result<LoadedFont, Box<dyn Error>> {
+    let data = choice.locate().data()?;
+    let font = Font::from_bytes(data.as_ref(), FontSettings::default())?;
+    let size = choice.size as f32;
+    // Text is laid out on lines, so fonts made only for vertical text
+    // can't be used
+    let line_metrics = font
+        .horizontal_line_metrics(size)
+        .ok_or("The font has no horizontal metrics")?;
+    Ok(LoadedFont {
+        font,
+        size,
+        line_metrics,
+    })
+}
+
+pub struct Fonts {
+    title: LoadedFont,
+    info: LoadedFont,
+    small: LoadedFont,
+}
+
+impl Fonts {
+    pub fn new(config: &FontConfig) -> Result<Self, Box<dyn Error>> {
+        Ok(Fonts {
+            title: load_font(&config.title)?,
+            info: load_font(&config.info)?,
+            small: load_font(&config.small)?,
+        })
+    }
+
+    // Renders the text on a transparent background, with the size of the
+    // image being the size of the text line
+    pub fn render(&self, text: &str, font_type: &FontType, color: Color) -> RgbaImage {
+        let LoadedFont {
+            font,
+            size,
+            line_metrics,
+        } = match font_type {
+            FontType::Title => &self.title,
+            FontType::Info => &self.info,
+            FontType::Small => &self.small,
+        };
+        let size = *size;
+        let ascent = line_metrics.ascent.ceil() as i32;
+        let height = (line_metrics.ascent - line_metrics.descent).ceil() as u32;
+
+        let glyphs: Vec<_> = text.chars().map(|c| font.rasterize(c, size)).collect();
+        let width = glyphs
+            .iter()
+            .map(|(metrics, _)| metrics.advance_width)
+            .sum::<f32>()
+            .ceil() as u32;
+
+        let mut image = RgbaImage::new(width.max(1), height.max(1));
+        let mut pen_x = 0.0f32;
+        for (metrics, coverage) in glyphs.iter() {
+            let glyph_left = pen_x as i32 + metrics.xmin;
+            let glyph_top = ascent - metrics.ymin - metrics.height as i32;
+            for row in 0..metrics.height {
+                for column in 0..metrics.width {
+                    let alpha = coverage[row * metrics.width + column];
+                    let x = glyph_left + column as i32;
+                    let y = glyph_top + row as i32;
+                    if alpha > 0 && x >= 0 && y >= 0 && (x as u32) < width && (y as u32) < height {
+                        let alpha = (alpha as u32 * color.a as u32 / 255) as u8;
+                        image.put_pixel(
+                            x as u32,
+                            y as u32,
+                            Rgba([color.r, color.g, color.b, alpha]),
+                        );
+                    }
+                }
+            }
+            pen_x += metrics.advance_width;
+        }
+        image
+    }
+}
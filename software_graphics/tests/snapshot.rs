@@ -0,0 +1,276 @@
+use std::path::{Path, PathBuf};
+
+use ecs_components::{Rotation, Sprite};
+use geometry::Position;
+use render::{Color, FontType, Renderer, SpriteOptions, TextPosition};
+use software_graphics::SoftwareRenderer;
+use tempfile::TempDir;
+
+fn sprite_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets")
+}
+
+fn snapshot_path(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("snapshots")
+        .join(format!("{}.png", name))
+}
+
+// Compares the rendered image with the stored snapshot. Run the tests with
+// UPDATE_SNAPSHOTS=1 to store the current output as the new snapshot. A
+// missing snapshot fails, so one that was never committed is noticed.
+fn assert_snapshot(renderer: &SoftwareRenderer, name: &str) {
+    let path = snapshot_path(name);
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        renderer.save_png(&path).unwrap();
+        return;
+    }
+    assert!(
+        path.exists(),
+        "Missing snapshot {}, run the tests with UPDATE_SNAPSHOTS=1 to make it",
+        path.display()
+    );
+    let expected = image::open(&path).unwrap().to_rgba8();
+    if expected != *renderer.image() {
+        let actual_path = path.with_extension("actual.png");
+        renderer.save_png(&actual_path).unwrap();
+        panic!(
+            "Rendering differs from {}, the result was written to {}",
+            path.display(),
+            actual_path.display()
+        );
+    }
+}
+
+#[test]
+fn test_scene_snapshot() {
+    let mut renderer = SoftwareRenderer::new((300, 150), &sprite_dir()).unwrap();
+    renderer.clear();
+    renderer.draw_circle(20.0, 20.0, 4, Color::rgb(150, 200, 200));
+    renderer.draw_circle(280.0, 130.0, 2, Color::rgb(150, 200, 200));
+    let plain = SpriteOptions::default();
+    renderer
+        .draw_sprite(&Sprite::Player, Position(10.0, 60.0), &plain)
+        .unwrap();
+    renderer
+        .draw_sprite(&Sprite::UFO, Position(200.0, 20.0), &plain)
+        .unwrap();
+    renderer
+        .draw_sprite(&Sprite::BasicShot, Position(140.0, 85.0), &plain)
+        .unwrap();
+    let rotated = SpriteOptions {
+        rotation: Some(Rotation {
+            degrees: 30.0,
+            pivot: None,
+        }),
+        ..SpriteOptions::default()
+    };
+    renderer
+        .draw_sprite(&Sprite::UFO, Position(200.0, 80.0), &rotated)
+        .unwrap();
+    renderer
+        .draw_sprite(&Sprite::Glow, Position(140.0, 20.0), &plain)
+        .unwrap();
+    renderer
+        .draw_sprite(&Sprite::Glow, Position(150.0, 25.0), &plain)
+        .unwrap();
+    renderer.present();
+    assert_eq!(renderer.frames_presented(), 1);
+    assert_snapshot(&renderer, "scene");
+}
+
+#[test]
+fn test_sprite_sizes_match_images() {
+    let renderer = SoftwareRenderer::new((10, 10), &sprite_dir()).unwrap();
+    let sizes = renderer.entity_sizes().unwrap();
+    assert_eq!(sizes.ufo_size, (64, 32));
+    assert_eq!(sizes.player_size, (117, 55));
+}
+
+// Copies the sprite images to a new directory, removed when dropped
+fn copy_sprite_dir() -> TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    for entry in std::fs::read_dir(sprite_dir()).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension() == Some("png".as_ref()) {
+            std::fs::copy(&path, dir.path().join(path.file_name().unwrap())).unwrap();
+        }
+    }
+    dir
+}
+
+// Splits the UFO into a left and a right half
+fn write_ufo_sheet(dir: &Path) {
+    std::fs::write(
+        dir.join("ufo.json"),
+        r#"{ "frames": [ { "x": 0, "y": 0, "w": 32, "h": 32 },
+                         { "x": 32, "y": 0, "w": 32, "h": 32 } ] }"#,
+    )
+    .unwrap();
+}
+
+fn sprite_dir_with_sheet() -> TempDir {
+    let dir = copy_sprite_dir();
+    write_ufo_sheet(dir.path());
+    dir
+}
+
+#[test]
+fn test_sprite_sheet_frames() {
+    let dir = sprite_dir_with_sheet();
+    let mut renderer = SoftwareRenderer::new((32, 32), dir.path()).unwrap();
+    assert_eq!(renderer.sprite_size(&Sprite::UFO).unwrap(), (32, 32));
+
+    let ufo = image::open(sprite_dir().join("ufo.png"))
+        .unwrap()
+        .to_rgba8();
+    for frame in 0..2 {
+        renderer.clear();
+        let options = SpriteOptions {
+            frame,
+            ..SpriteOptions::default()
+        };
+        renderer
+            .draw_sprite(&Sprite::UFO, Position(0.0, 0.0), &options)
+            .unwrap();
+        for (x, y, pixel) in renderer.image().enumerate_pixels() {
+            let source = ufo.get_pixel(x + 32 * frame as u32, y);
+            if source[3] == 255 {
+                assert_eq!(
+                    pixel.0[0..3],
+                    source.0[0..3],
+                    "frame {} at {},{}",
+                    frame,
+                    x,
+                    y
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_reload_sprite() {
+    let dir = copy_sprite_dir();
+    let mut renderer = SoftwareRenderer::new((32, 32), dir.path()).unwrap();
+    assert_eq!(renderer.sprite_size(&Sprite::UFO).unwrap(), (64, 32));
+    write_ufo_sheet(dir.path());
+    renderer.reload("ufo.json").unwrap();
+    assert_eq!(renderer.sprite_size(&Sprite::UFO).unwrap(), (32, 32));
+    renderer.reload("starfield.json").unwrap();
+}
+
+#[test]
+fn test_scaled_sprite_covers_scaled_area() {
+    let mut renderer = SoftwareRenderer::new((200, 100), &sprite_dir()).unwrap();
+    renderer.clear();
+    let options = SpriteOptions {
+        scale: 2.0,
+        ..SpriteOptions::default()
+    };
+    renderer
+        .draw_sprite(&Sprite::BasicShot, Position(10.0, 20.0), &options)
+        .unwrap();
+    let drawn: Vec<_> = renderer
+        .image()
+        .enumerate_pixels()
+        .filter(|(_, _, pixel)| pixel.0[0..3] != [0, 0, 0])
+        .map(|(x, y, _)| (x, y))
+        .collect();
+    let shot = image::open(sprite_dir().join("basic_shot.png"))
+        .unwrap()
+        .to_rgba8();
+    let visible = shot
+        .pixels()
+        .filter(|p| p.0[3] > 0 && p.0[0..3] != [0, 0, 0]);
+    assert_eq!(drawn.len(), visible.count() * 4);
+    assert!(drawn
+        .iter()
+        .all(|&(x, y)| (10..26).contains(&x) && (20..36).contains(&y)));
+}
+
+#[test]
+fn test_tint_removes_color_channels() {
+    let mut renderer = SoftwareRenderer::new((100, 100), &sprite_dir()).unwrap();
+    renderer.clear();
+    let options = SpriteOptions {
+        tint: Color::rgb(0, 255, 0),
+        ..SpriteOptions::default()
+    };
+    renderer
+        .draw_sprite(&Sprite::Glow, Position(10.0, 10.0), &options)
+        .unwrap();
+    let pixels: Vec<_> = renderer.image().pixels().collect();
+    assert!(pixels.iter().any(|p| p.0[1] > 0));
+    assert!(pixels.iter().all(|p| p.0[0] == 0 && p.0[2] == 0));
+
+    renderer.clear();
+    let options = SpriteOptions {
+        tint: Color::rgba(255, 255, 255, 0),
+        ..SpriteOptions::default()
+    };
+    renderer
+        .draw_sprite(&Sprite::Glow, Position(10.0, 10.0), &options)
+        .unwrap();
+    assert!(renderer.image().pixels().all(|p| p.0[0..3] == [0, 0, 0]));
+}
+
+#[test]
+fn test_text_needs_a_font() {
+    let mut renderer = SoftwareRenderer::new((10, 10), &sprite_dir()).unwrap();
+    let result = renderer.draw_text(
+        "Rideways",
+        TextPosition::Center(5, 5),
+        Color::WHITE,
+        FontType::Info,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_text_with_bundled_font() {
+    let mut renderer = SoftwareRenderer::with_bundled_font((400, 200), &sprite_dir()).unwrap();
+    renderer.clear();
+    renderer
+        .draw_text(
+            "Score: 10",
+            TextPosition::TopRight(390, 10),
+            Color::WHITE,
+            FontType::Info,
+        )
+        .unwrap();
+    let drawn: Vec<_> = renderer
+        .image()
+        .enumerate_pixels()
+        .filter(|(_, _, pixel)| pixel.0[0..3] != [0, 0, 0])
+        .map(|(x, y, _)| (x, y))
+        .collect();
+    assert!(drawn.len() > 100);
+    assert!(drawn
+        .iter()
+        .all(|&(x, y)| x < 390 && (10..10 + 50).contains(&y)));
+    assert!(drawn.iter().any(|&(x, _)| x > 380));
+}
+
+#[test]
+fn test_text_color_changes_without_rendering_again() {
+    let mut renderer = SoftwareRenderer::with_bundled_font((200, 100), &sprite_dir()).unwrap();
+    let brightest = |renderer: &mut SoftwareRenderer, color: Color| {
+        renderer.clear();
+        renderer
+            .draw_text("+1", TextPosition::Center(100, 50), color, FontType::Info)
+            .unwrap();
+        renderer.image().pixels().map(|p| p.0).max().unwrap()
+    };
+    assert_eq!(
+        brightest(&mut renderer, Color::rgb(255, 0, 0))[0..3],
+        [255, 0, 0]
+    );
+    assert_eq!(
+        brightest(&mut renderer, Color::rgb(0, 0, 255))[0..3],
+        [0, 0, 255]
+    );
+    let faded = brightest(&mut renderer, Color::rgba(255, 255, 255, 128));
+    assert!(faded[0] > 100 && faded[0] < 160);
+}